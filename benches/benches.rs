@@ -163,6 +163,12 @@ fn get_mid(c: &mut Criterion) {
     .iter()
     {
         let pinned: PinnedDeque<u64> = (0..*n).map(|x| x as u64).collect();
+        let pow2: PinnedDeque<u64> = {
+            let mut res = PinnedDeque::with_pow2_capacity_per_chunk(256);
+            res.extend((0..*n).map(|x| x as u64));
+            res
+        };
+        let fixed: FixedChunkDeque<u64, 512> = (0..*n).map(|x| x as u64).collect();
         let vecdeque: VecDeque<u64> = (0..*n).map(|x| x as u64).collect();
         let vec: Vec<u64> = (0..*n).map(|x| x as u64).collect();
         let mid_idx = *n / 2;
@@ -171,6 +177,16 @@ fn get_mid(c: &mut Criterion) {
                 black_box(pinned.get(mid_idx));
             })
         });
+        group.bench_function(BenchmarkId::new("PinnedDeque(pow2)", n), |b| {
+            b.iter(|| {
+                black_box(pow2.get(mid_idx));
+            })
+        });
+        group.bench_function(BenchmarkId::new("FixedChunkDeque", n), |b| {
+            b.iter(|| {
+                black_box(fixed.get(mid_idx));
+            })
+        });
         group.bench_function(BenchmarkId::new("VecDeque", n), |b| {
             b.iter(|| {
                 black_box(vecdeque.get(mid_idx));
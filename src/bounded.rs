@@ -0,0 +1,146 @@
+use crate::*;
+
+/// What a bounded deque does when an element is pushed into it while it is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// The pushed element is rejected.
+    Reject,
+    /// The front element is evicted to make room.
+    EvictFront,
+    /// The back element is evicted to make room.
+    EvictBack,
+    /// Panics.
+    Panic,
+}
+
+impl<T> PinnedDeque<T>
+where
+    T: Sized,
+{
+    /// Creates an empty deque, which holds at most `max_len` elements,
+    /// with the adaptive capacity per chunk.
+    ///
+    /// All chunks are allocated at once, and no more allocations happen then.
+    ///
+    /// When the deque is full, `push_back()`, `push_front()` and their `try_` variants
+    /// follow `policy` and drop the element overflowing (i.e., the pushed one under
+    /// [`OverflowPolicy::Reject`] or the evicted one under the others),
+    /// except that the `try_` variants give a rejected element back as an error.
+    /// `push_back_bounded()` and `push_front_bounded()` give it back instead.
+    pub fn bounded(max_len: usize, policy: OverflowPolicy) -> Self {
+        Self::bounded_with_capacity_per_chunk(max_len, policy, Self::adaptive_capacity_per_chunk())
+    }
+
+    /// Like `bounded()`, but with the given capacity per chunk.
+    pub fn bounded_with_capacity_per_chunk(
+        max_len: usize,
+        policy: OverflowPolicy,
+        cap_per_chunk: u32,
+    ) -> Self {
+        let mut res = Self::with_capacity_per_chunk(cap_per_chunk);
+        res.max_len = max_len;
+        res.overflow_policy = policy;
+        // Elements of a full deque may be spread over a partial chunk at either end.
+        let n = if max_len == 0 {
+            0
+        } else {
+            max_len.div_ceil(cap_per_chunk as usize) + 1
+        };
+        res.freed.reserve_exact(n);
+        if let Err(err) = res.try_reserve_used(n) {
+            err.handle();
+        }
+        for _ in 0..n {
+            match res.try_new_chunk() {
+                Ok(chunk) => res.freed.push(chunk),
+                Err(err) => err.handle(),
+            }
+        }
+        res
+    }
+
+    /// Returns the maximum number of elements if the deque is bounded.
+    pub fn max_len(&self) -> Option<usize> {
+        if self.is_bounded() {
+            Some(self.max_len)
+        } else {
+            None
+        }
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len() >= self.max_len
+    }
+
+    /// Returns how many elements can be pushed before the deque is full.
+    ///
+    /// It is `usize::MAX - len()` if the deque is unbounded.
+    pub fn remaining(&self) -> usize {
+        self.max_len - self.len()
+    }
+
+    /// Pushes an element at the back.
+    ///
+    /// If the deque is full, the element overflowing is returned.
+    pub fn push_back_bounded(&mut self, elem: T) -> Option<T> {
+        if self.is_full() {
+            self.overflow_back(elem)
+        } else {
+            self.push_back_unbounded(elem);
+            None
+        }
+    }
+
+    /// Pushes an element at the front.
+    ///
+    /// If the deque is full, the element overflowing is returned.
+    pub fn push_front_bounded(&mut self, elem: T) -> Option<T> {
+        if self.is_full() {
+            self.overflow_front(elem)
+        } else {
+            self.push_front_unbounded(elem);
+            None
+        }
+    }
+
+    pub(crate) fn is_bounded(&self) -> bool {
+        self.max_len != usize::MAX
+    }
+
+    /// Returns whether an element pushed into the full deque is rejected.
+    pub(crate) fn rejects_overflow(&self) -> bool {
+        self.overflow_policy == OverflowPolicy::Reject || self.max_len == 0
+    }
+
+    pub(crate) fn overflow_back(&mut self, elem: T) -> Option<T> {
+        let evicted = match self.overflow_policy {
+            OverflowPolicy::Reject => return Some(elem),
+            OverflowPolicy::EvictFront => self.pop_front(),
+            OverflowPolicy::EvictBack => self.pop_back(),
+            OverflowPolicy::Panic => panic!("the deque is full (max_len: {})", self.max_len),
+        };
+        if evicted.is_some() {
+            self.push_back_unbounded(elem);
+            evicted
+        } else {
+            // `max_len` is 0.
+            Some(elem)
+        }
+    }
+
+    pub(crate) fn overflow_front(&mut self, elem: T) -> Option<T> {
+        let evicted = match self.overflow_policy {
+            OverflowPolicy::Reject => return Some(elem),
+            OverflowPolicy::EvictFront => self.pop_front(),
+            OverflowPolicy::EvictBack => self.pop_back(),
+            OverflowPolicy::Panic => panic!("the deque is full (max_len: {})", self.max_len),
+        };
+        if evicted.is_some() {
+            self.push_front_unbounded(elem);
+            evicted
+        } else {
+            // `max_len` is 0.
+            Some(elem)
+        }
+    }
+}
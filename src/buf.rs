@@ -0,0 +1,69 @@
+use crate::*;
+use bytes::{buf::UninitSlice, Buf, BufMut};
+#[cfg(feature = "std")]
+use std::io::IoSlice;
+
+impl PinnedDeque<u8> {
+    /// Returns a view popping bytes at the front, which implements [`Buf`].
+    pub fn reader(&mut self) -> ByteReader<'_> {
+        ByteReader { deque: self }
+    }
+
+    /// Returns a view pushing bytes at the back, which implements [`BufMut`].
+    pub fn writer(&mut self) -> ByteWriter<'_> {
+        ByteWriter { deque: self }
+    }
+}
+
+/// Reads bytes at the front of a deque chunk by chunk.
+pub struct ByteReader<'a> {
+    deque: &'a mut PinnedDeque<u8>,
+}
+
+impl Buf for ByteReader<'_> {
+    fn remaining(&self) -> usize {
+        self.deque.len()
+    }
+
+    fn chunk(&self) -> &[u8] {
+        self.deque.front_bytes()
+    }
+
+    fn advance(&mut self, cnt: usize) {
+        self.deque.advance_bytes(cnt);
+    }
+
+    #[cfg(feature = "std")]
+    fn chunks_vectored<'a>(&'a self, dst: &mut [IoSlice<'a>]) -> usize {
+        self.deque.as_io_slices(dst)
+    }
+}
+
+/// Writes bytes into spare capacity at the back of a deque chunk by chunk.
+///
+/// It writes no more than [`PinnedDeque::remaining()`] bytes, even if the deque evicts elements.
+pub struct ByteWriter<'a> {
+    deque: &'a mut PinnedDeque<u8>,
+}
+
+unsafe impl BufMut for ByteWriter<'_> {
+    fn remaining_mut(&self) -> usize {
+        self.deque.remaining()
+    }
+
+    /// Bytes beyond `remaining_mut()` are ignored.
+    unsafe fn advance_mut(&mut self, cnt: usize) {
+        self.deque
+            .commit_back_bytes(cnt.min(self.deque.remaining()));
+    }
+
+    fn chunk_mut(&mut self) -> &mut UninitSlice {
+        let remaining = self.deque.remaining();
+        if remaining == 0 {
+            return UninitSlice::new(&mut []);
+        }
+        let spare = self.deque.spare_back_bytes();
+        let len = spare.len().min(remaining);
+        UninitSlice::new(&mut spare[..len])
+    }
+}
@@ -0,0 +1,314 @@
+use crate::{chunk::Chunk, *};
+use core::{
+    fmt,
+    ops::{Deref, DerefMut},
+    slice,
+};
+#[cfg(feature = "std")]
+use std::io;
+
+/// A deque of bytes, which reads and writes whole runs of bytes in chunks.
+///
+/// It dereferences to [`PinnedDeque<u8>`] for everything else.
+/// Memory of chunks is always initialized, so bytes can be read into their spare capacity.
+pub struct PinnedByteDeque {
+    inner: PinnedDeque<u8>,
+}
+
+impl PinnedByteDeque {
+    pub fn new() -> Self {
+        Self {
+            inner: PinnedDeque::new(),
+        }
+    }
+
+    pub fn with_capacity_per_chunk(cap_per_chunk: u32) -> Self {
+        Self {
+            inner: PinnedDeque::with_capacity_per_chunk(cap_per_chunk),
+        }
+    }
+
+    pub fn into_inner(self) -> PinnedDeque<u8> {
+        self.inner
+    }
+
+    /// Copies bytes at the back, filling the tail of the back chunk first.
+    ///
+    /// A bounded deque follows its overflow policy as if bytes were pushed one by one,
+    /// so under [`OverflowPolicy::Reject`] bytes which do not fit are dropped.
+    pub fn extend_from_slice(&mut self, bytes: &[u8]) {
+        let mut bytes = &bytes[bytes.len().saturating_sub(self.inner.max_bytes_kept())..];
+        while !bytes.is_empty() {
+            let room = self.inner.room_for_bytes(bytes.len());
+            if room == 0 {
+                break;
+            }
+            let spare = self.inner.spare_back_bytes();
+            let n = spare.len().min(room);
+            spare[..n].copy_from_slice(&bytes[..n]);
+            self.inner.commit_back_bytes(n);
+            bytes = &bytes[n..];
+        }
+    }
+
+    /// Reads bytes from `reader` into the spare capacity of the back chunk directly,
+    /// and returns how many bytes are read.
+    ///
+    /// It calls `reader.read()` once, and it reads no more than a chunk.
+    /// A bounded deque follows its overflow policy as `extend_from_slice()` does,
+    /// except that under [`OverflowPolicy::Reject`] it reads no more than
+    /// [`remaining()`](PinnedDeque::remaining) bytes, i.e., nothing if it is full.
+    #[cfg(feature = "std")]
+    pub fn read_from<R: io::Read + ?Sized>(&mut self, reader: &mut R) -> io::Result<usize> {
+        let room = self.inner.room_for_bytes(usize::MAX);
+        let spare = self.inner.spare_back_bytes();
+        let len = spare.len().min(room);
+        let n = reader.read(&mut spare[..len])?;
+        self.inner.commit_back_bytes(n);
+        Ok(n)
+    }
+
+    /// Returns the index of the first occurrence of `byte`.
+    pub fn find_byte(&self, byte: u8) -> Option<usize> {
+        let mut offset = 0;
+        for chunk in self.inner.used.iter() {
+            let chunk = unsafe { &*(*chunk as *const Chunk<u8>) }.as_slice();
+            if let Some(idx) = memchr::memchr(byte, chunk) {
+                return Some(offset + idx);
+            }
+            offset += chunk.len();
+        }
+        None
+    }
+
+    /// Returns bytes in the front chunk.
+    pub fn front_chunk(&self) -> &[u8] {
+        self.inner.front_bytes()
+    }
+
+    /// Pops `n` bytes at the front.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are less than `n` bytes.
+    pub fn advance(&mut self, n: usize) {
+        self.inner.advance_bytes(n)
+    }
+}
+
+impl PinnedDeque<u8> {
+    /// Fills `dst` with bytes in chunks from the front, one chunk per `IoSlice`,
+    /// and returns how many slices are filled.
+    ///
+    /// So, a single `write_vectored()` can flush the whole deque.
+    #[cfg(feature = "std")]
+    pub fn as_io_slices<'a>(&'a self, dst: &mut [io::IoSlice<'a>]) -> usize {
+        let mut n = 0;
+        for (dst, chunk) in dst.iter_mut().zip(self.used.iter()) {
+            let chunk = unsafe { &*(*chunk as *const Chunk<u8>) };
+            *dst = io::IoSlice::new(chunk.as_slice());
+            n += 1;
+        }
+        n
+    }
+
+    pub(crate) fn front_bytes(&self) -> &[u8] {
+        match self.used.front() {
+            Some(front_chunk) => unsafe { &*(*front_chunk as *const Chunk<u8>) }.as_slice(),
+            None => &[],
+        }
+    }
+
+    pub(crate) fn advance_bytes(&mut self, n: usize) {
+        assert!(n <= self.len(), "advancing past the end of the deque");
+        let mut n = n;
+        while n > 0 {
+            let front_chunk_ptr = unsafe { *self.used.front().unwrap_unchecked() };
+            let front_chunk = unsafe { &mut *front_chunk_ptr };
+            // Bytes need no dropping, so moving `start` is enough, even in a shared chunk.
+            let m = front_chunk.len().min(n);
+            front_chunk.start += m as u32;
+            self.size -= m;
+            self.head_seq = self.head_seq.wrapping_add(m as u64);
+            n -= m;
+            if front_chunk.len() == 0 {
+                self.detach_front();
+                self.recycle(front_chunk_ptr);
+            }
+        }
+    }
+
+    /// Returns the spare capacity of the back chunk, or of a new chunk if it is full.
+    pub(crate) fn spare_back_bytes(&mut self) -> &mut [u8] {
+        let cap_per_chunk = self.cap_per_chunk;
+        self.unshare_back_for_push();
+        let chunk = match self.used.back() {
+            Some(back_chunk) if unsafe { &**back_chunk }.end < cap_per_chunk => *back_chunk,
+            _ => {
+                let chunk = self.spare_chunk();
+                unsafe { &mut *chunk }.reset_for_back_insertion();
+                chunk
+            }
+        };
+        unsafe {
+            let end = (*chunk).end;
+            let elems = Chunk::elems(chunk).add(end as usize) as *mut u8;
+            slice::from_raw_parts_mut(elems, (cap_per_chunk - end) as usize)
+        }
+    }
+
+    /// Drops `n` bytes at the back.
+    fn truncate_back_bytes(&mut self, n: usize) {
+        let mut n = n;
+        while n > 0 {
+            let back_chunk_ptr = unsafe { *self.used.back().unwrap_unchecked() };
+            let back_chunk = unsafe { &mut *back_chunk_ptr };
+            let m = back_chunk.len().min(n);
+            back_chunk.end -= m as u32;
+            self.size -= m;
+            n -= m;
+            if back_chunk.len() == 0 {
+                self.detach_back();
+                self.recycle(back_chunk_ptr);
+            }
+        }
+    }
+
+    /// Returns how many of the last bytes written at once can be kept,
+    /// which is less than all of them only if the front is evicted for them.
+    pub(crate) fn max_bytes_kept(&self) -> usize {
+        if self.overflow_policy == OverflowPolicy::EvictFront {
+            self.max_len
+        } else {
+            usize::MAX
+        }
+    }
+
+    /// Returns how many of `n` bytes can be written at the back,
+    /// which is capped at `remaining()` only if the deque rejects overflowing bytes.
+    pub(crate) fn room_for_bytes(&self, n: usize) -> usize {
+        if self.rejects_overflow() {
+            n.min(self.remaining())
+        } else {
+            n
+        }
+    }
+
+    /// Appends `n` bytes written into the slice returned by `spare_back_bytes()`.
+    ///
+    /// If the deque overflows, it follows its overflow policy as if bytes were pushed one by one.
+    pub(crate) fn commit_back_bytes(&mut self, n: usize) {
+        if n == 0 {
+            return;
+        }
+        let cap_per_chunk = self.cap_per_chunk;
+        let chunk = match self.used.back() {
+            Some(back_chunk) if unsafe { &**back_chunk }.end < cap_per_chunk => *back_chunk,
+            _ => {
+                let chunk = self.fetch_a_freed_chunk();
+                unsafe { &mut *chunk }.reset_for_back_insertion();
+                self.attach_back(chunk);
+                chunk
+            }
+        };
+        let chunk = unsafe { &mut *chunk };
+        debug_assert!(chunk.end as usize + n <= cap_per_chunk as usize);
+        chunk.end += n as u32;
+        self.size += n;
+        if self.size > self.max_len {
+            self.overflow_bytes(self.size - self.max_len);
+        }
+    }
+
+    /// Gets rid of `excess` bytes just appended beyond `max_len`.
+    fn overflow_bytes(&mut self, excess: usize) {
+        match self.overflow_policy {
+            OverflowPolicy::EvictFront => self.advance_bytes(excess),
+            OverflowPolicy::Reject => self.truncate_back_bytes(excess),
+            OverflowPolicy::EvictBack => {
+                // Each byte evicts the one before it, so only the last one is left.
+                let last = unsafe { *self.back().unwrap_unchecked() };
+                self.truncate_back_bytes(excess);
+                if let Some(back) = self.back_mut() {
+                    *back = last;
+                }
+            }
+            OverflowPolicy::Panic => {
+                self.truncate_back_bytes(excess);
+                panic!("the deque is full (max_len: {})", self.max_len);
+            }
+        }
+    }
+}
+
+impl Default for PinnedByteDeque {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deref for PinnedByteDeque {
+    type Target = PinnedDeque<u8>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl DerefMut for PinnedByteDeque {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+impl From<PinnedDeque<u8>> for PinnedByteDeque {
+    fn from(inner: PinnedDeque<u8>) -> Self {
+        Self { inner }
+    }
+}
+
+impl fmt::Debug for PinnedByteDeque {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl io::Write for PinnedByteDeque {
+    /// Writes no more than `remaining()` bytes if the deque rejects overflowing bytes.
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.inner.room_for_bytes(buf.len());
+        self.extend_from_slice(&buf[..n]);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl io::Read for PinnedByteDeque {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut n = 0;
+        while n < buf.len() && !self.is_empty() {
+            let front_chunk = self.front_chunk();
+            let m = front_chunk.len().min(buf.len() - n);
+            buf[n..n + m].copy_from_slice(&front_chunk[..m]);
+            self.advance(m);
+            n += m;
+        }
+        Ok(n)
+    }
+}
+
+#[cfg(feature = "std")]
+impl io::BufRead for PinnedByteDeque {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        Ok(self.front_chunk())
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.advance(amt);
+    }
+}
@@ -0,0 +1,491 @@
+//! Multi-producer, single-consumer channels built on [`PinnedDeque`].
+//!
+//! Messages stay in slots of a pinned deque from sending to receiving.
+//! So, [`Receiver::recv_ref()`] lends the front message in place,
+//! rather than moving it out.
+
+use crate::{OverflowPolicy, PinnedDeque};
+use std::{
+    cell::Cell,
+    error::Error,
+    fmt,
+    future::Future,
+    marker::PhantomData,
+    ops::Deref,
+    pin::Pin,
+    sync::{Arc, Condvar, Mutex, MutexGuard},
+    task::{Context, Poll, Waker},
+};
+
+/// Creates a channel holding any number of messages.
+pub fn unbounded<T>() -> (Sender<T>, Receiver<T>) {
+    new_channel(PinnedDeque::new())
+}
+
+/// Creates a channel holding at most `cap` messages.
+///
+/// All chunks are allocated at once, and sending never allocates memory then.
+pub fn bounded<T>(cap: usize) -> (Sender<T>, Receiver<T>) {
+    assert!(cap > 0, "capacity of a bounded channel must be positive");
+    new_channel(PinnedDeque::bounded(cap, OverflowPolicy::Reject))
+}
+
+fn new_channel<T>(queue: PinnedDeque<T>) -> (Sender<T>, Receiver<T>) {
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State {
+            queue: Queue(queue),
+            senders: 1,
+            receiver: true,
+            recv_waker: None,
+            send_wakers: Vec::new(),
+        }),
+        not_empty: Condvar::new(),
+        not_full: Condvar::new(),
+    });
+    let sender = Sender {
+        shared: shared.clone(),
+    };
+    let receiver = Receiver {
+        shared,
+        _not_sync: PhantomData,
+    };
+    (sender, receiver)
+}
+
+struct Shared<T> {
+    state: Mutex<State<T>>,
+    not_empty: Condvar,
+    not_full: Condvar,
+}
+
+struct State<T> {
+    queue: Queue<T>,
+    senders: usize,
+    receiver: bool,
+    recv_waker: Option<Waker>,
+    send_wakers: Vec<Waker>,
+}
+
+/// The deque is reachable only through the mutex, and no snapshot is ever taken of it.
+struct Queue<T>(PinnedDeque<T>);
+
+unsafe impl<T: Send> Send for Queue<T> {}
+
+impl<T> Shared<T> {
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Pushes a message, or gives it back if the channel is full or disconnected.
+    fn try_send(&self, elem: T) -> Result<(), TrySendError<T>> {
+        let mut state = self.lock();
+        if !state.receiver {
+            return Err(TrySendError::Disconnected(elem));
+        }
+        if let Some(elem) = state.queue.0.push_back_bounded(elem) {
+            return Err(TrySendError::Full(elem));
+        }
+        if let Some(waker) = state.recv_waker.take() {
+            waker.wake();
+        }
+        drop(state);
+        self.not_empty.notify_one();
+        Ok(())
+    }
+
+    fn try_recv(&self) -> Result<T, TryRecvError> {
+        let mut state = self.lock();
+        match state.queue.0.pop_front() {
+            Some(elem) => {
+                self.after_pop(state);
+                Ok(elem)
+            }
+            None if state.senders == 0 => Err(TryRecvError::Disconnected),
+            None => Err(TryRecvError::Empty),
+        }
+    }
+
+    /// Returns the front message without popping it.
+    fn try_peek(&self) -> Result<*const T, TryRecvError> {
+        let state = self.lock();
+        match state.queue.0.front() {
+            Some(elem) => Ok(elem as *const T),
+            None if state.senders == 0 => Err(TryRecvError::Disconnected),
+            None => Err(TryRecvError::Empty),
+        }
+    }
+
+    fn after_pop(&self, mut state: MutexGuard<'_, State<T>>) {
+        let send_wakers = std::mem::take(&mut state.send_wakers);
+        drop(state);
+        for waker in send_wakers {
+            waker.wake();
+        }
+        self.not_full.notify_one();
+    }
+}
+
+/// The sending half of a channel.
+///
+/// It can be cloned to send from multiple threads.
+pub struct Sender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Sender<T> {
+    /// Sends a message, blocking while the channel is full.
+    ///
+    /// It fails if the receiver is dropped.
+    pub fn send(&self, elem: T) -> Result<(), SendError<T>> {
+        let mut elem = elem;
+        loop {
+            match self.shared.try_send(elem) {
+                Ok(()) => return Ok(()),
+                Err(TrySendError::Disconnected(e)) => return Err(SendError(e)),
+                Err(TrySendError::Full(e)) => elem = e,
+            }
+            let state = self.shared.lock();
+            if state.receiver && state.queue.0.is_full() {
+                drop(self.shared.not_full.wait(state));
+            }
+        }
+    }
+
+    /// Sends a message without blocking.
+    pub fn try_send(&self, elem: T) -> Result<(), TrySendError<T>> {
+        self.shared.try_send(elem)
+    }
+
+    /// Returns a future sending a message, which waits while the channel is full.
+    pub fn send_async(&self, elem: T) -> SendFuture<'_, T> {
+        SendFuture {
+            sender: self,
+            elem: Some(elem),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.shared.lock().queue.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the maximum number of messages if the channel is bounded.
+    pub fn capacity(&self) -> Option<usize> {
+        self.shared.lock().queue.0.max_len()
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        self.shared.lock().senders += 1;
+        Self {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.senders -= 1;
+        if state.senders > 0 {
+            return;
+        }
+        let recv_waker = state.recv_waker.take();
+        drop(state);
+        if let Some(waker) = recv_waker {
+            waker.wake();
+        }
+        self.shared.not_empty.notify_all();
+    }
+}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender").finish_non_exhaustive()
+    }
+}
+
+/// The receiving half of a channel.
+pub struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+    _not_sync: PhantomData<Cell<()>>,
+}
+
+impl<T> Receiver<T> {
+    /// Receives a message, blocking while the channel is empty.
+    ///
+    /// It fails if the channel is empty and all senders are dropped.
+    pub fn recv(&self) -> Result<T, RecvError> {
+        loop {
+            match self.shared.try_recv() {
+                Ok(elem) => return Ok(elem),
+                Err(TryRecvError::Disconnected) => return Err(RecvError),
+                Err(TryRecvError::Empty) => {}
+            }
+            self.wait_for_message();
+        }
+    }
+
+    /// Receives a message without blocking.
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        self.shared.try_recv()
+    }
+
+    /// Returns a future receiving a message, which waits while the channel is empty.
+    pub fn recv_async(&self) -> RecvFuture<'_, T> {
+        RecvFuture { receiver: self }
+    }
+
+    /// Like `recv()`, but lends the message in place, rather than moving it out.
+    ///
+    /// The message is popped when the guard is dropped.
+    /// Meanwhile, senders go on pushing behind it.
+    pub fn recv_ref(&mut self) -> Result<RecvRef<'_, T>, RecvError> {
+        loop {
+            match self.shared.try_peek() {
+                Ok(elem) => {
+                    return Ok(RecvRef {
+                        receiver: self,
+                        elem,
+                    })
+                }
+                Err(TryRecvError::Disconnected) => return Err(RecvError),
+                Err(TryRecvError::Empty) => {}
+            }
+            self.wait_for_message();
+        }
+    }
+
+    /// Like `try_recv()`, but lends the message in place, rather than moving it out.
+    pub fn try_recv_ref(&mut self) -> Result<RecvRef<'_, T>, TryRecvError> {
+        let elem = self.shared.try_peek()?;
+        Ok(RecvRef {
+            receiver: self,
+            elem,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.shared.lock().queue.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the maximum number of messages if the channel is bounded.
+    pub fn capacity(&self) -> Option<usize> {
+        self.shared.lock().queue.0.max_len()
+    }
+
+    fn wait_for_message(&self) {
+        let state = self.shared.lock();
+        if state.senders > 0 && state.queue.0.is_empty() {
+            drop(self.shared.not_empty.wait(state));
+        }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.receiver = false;
+        let send_wakers = std::mem::take(&mut state.send_wakers);
+        drop(state);
+        for waker in send_wakers {
+            waker.wake();
+        }
+        self.shared.not_full.notify_all();
+    }
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver").finish_non_exhaustive()
+    }
+}
+
+/// The front message of a channel, lent by [`Receiver::recv_ref()`].
+///
+/// Senders only push at the back, and the receiver is borrowed by the guard.
+/// So, the message is neither moved nor popped by anybody else.
+pub struct RecvRef<'a, T> {
+    receiver: &'a mut Receiver<T>,
+    elem: *const T,
+}
+
+impl<T> Deref for RecvRef<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*self.elem }
+    }
+}
+
+impl<T> Drop for RecvRef<'_, T> {
+    fn drop(&mut self) {
+        let shared = &self.receiver.shared;
+        let mut state = shared.lock();
+        drop(state.queue.0.pop_front());
+        shared.after_pop(state);
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for RecvRef<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+/// The future returned by [`Sender::send_async()`].
+pub struct SendFuture<'a, T> {
+    sender: &'a Sender<T>,
+    elem: Option<T>,
+}
+
+// The message is never pinned.
+impl<T> Unpin for SendFuture<'_, T> {}
+
+impl<T> Future for SendFuture<'_, T> {
+    type Output = Result<(), SendError<T>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let elem = this
+            .elem
+            .take()
+            .expect("`SendFuture` polled after completion");
+        let shared = &this.sender.shared;
+        let elem = match shared.try_send(elem) {
+            Ok(()) => return Poll::Ready(Ok(())),
+            Err(TrySendError::Disconnected(elem)) => return Poll::Ready(Err(SendError(elem))),
+            Err(TrySendError::Full(elem)) => elem,
+        };
+        let mut state = shared.lock();
+        if !state.receiver || !state.queue.0.is_full() {
+            // Things changed in between. Poll again at once.
+            cx.waker().wake_by_ref();
+        } else if !state
+            .send_wakers
+            .iter()
+            .any(|waker| waker.will_wake(cx.waker()))
+        {
+            // Polling again before a wake-up must not add the same waker again.
+            state.send_wakers.push(cx.waker().clone());
+        }
+        this.elem = Some(elem);
+        Poll::Pending
+    }
+}
+
+/// The future returned by [`Receiver::recv_async()`].
+pub struct RecvFuture<'a, T> {
+    receiver: &'a Receiver<T>,
+}
+
+impl<T> Future for RecvFuture<'_, T> {
+    type Output = Result<T, RecvError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let shared = &self.receiver.shared;
+        let mut state = shared.lock();
+        if let Some(elem) = state.queue.0.pop_front() {
+            shared.after_pop(state);
+            return Poll::Ready(Ok(elem));
+        }
+        if state.senders == 0 {
+            return Poll::Ready(Err(RecvError));
+        }
+        state.recv_waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+/// Returned by sending on a channel whose receiver is dropped.
+///
+/// The message is given back.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct SendError<T>(pub T);
+
+impl<T> fmt::Debug for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SendError").finish_non_exhaustive()
+    }
+}
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "sending on a disconnected channel")
+    }
+}
+
+impl<T> Error for SendError<T> {}
+
+/// Returned by `try_send()`. The message is given back.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum TrySendError<T> {
+    Full(T),
+    Disconnected(T),
+}
+
+impl<T> TrySendError<T> {
+    pub fn into_inner(self) -> T {
+        match self {
+            Self::Full(elem) | Self::Disconnected(elem) => elem,
+        }
+    }
+}
+
+impl<T> fmt::Debug for TrySendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Full(_) => write!(f, "Full(..)"),
+            Self::Disconnected(_) => write!(f, "Disconnected(..)"),
+        }
+    }
+}
+
+impl<T> fmt::Display for TrySendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Full(_) => write!(f, "sending on a full channel"),
+            Self::Disconnected(_) => write!(f, "sending on a disconnected channel"),
+        }
+    }
+}
+
+impl<T> Error for TrySendError<T> {}
+
+/// Returned by receiving on a channel which is empty and whose senders are all dropped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecvError;
+
+impl fmt::Display for RecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "receiving on an empty and disconnected channel")
+    }
+}
+
+impl Error for RecvError {}
+
+/// Returned by `try_recv()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TryRecvError {
+    Empty,
+    Disconnected,
+}
+
+impl fmt::Display for TryRecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "receiving on an empty channel"),
+            Self::Disconnected => write!(f, "receiving on an empty and disconnected channel"),
+        }
+    }
+}
+
+impl Error for TryRecvError {}
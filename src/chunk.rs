@@ -1,6 +1,8 @@
-use std::{
-    alloc::{alloc_zeroed, Layout},
-    mem::{size_of, MaybeUninit},
+use crate::TryReserveError;
+use alloc::alloc::{alloc_zeroed, dealloc, Layout};
+use core::{
+    mem::{align_of, size_of, MaybeUninit},
+    slice,
 };
 
 pub(crate) struct Chunk<T: Sized> {
@@ -9,9 +11,47 @@ pub(crate) struct Chunk<T: Sized> {
     pub(crate) _elems: [MaybeUninit<T>; 0],
 }
 
+/// Chunks of a page or larger are aligned to memory pages.
+#[cfg(feature = "std")]
+pub(crate) fn page_size() -> usize {
+    page_size::get()
+}
+
+/// Without `std`, the size of memory pages cannot be queried.
+/// It defaults to 4KiB and can be overridden at compile time
+/// by the environment variable `PINNED_DEQUE_PAGE_SIZE`.
+#[cfg(not(feature = "std"))]
+pub(crate) fn page_size() -> usize {
+    const PAGE_SIZE: usize = match option_env!("PINNED_DEQUE_PAGE_SIZE") {
+        Some(s) => parse_page_size(s),
+        None => 4096,
+    };
+    PAGE_SIZE
+}
+
+#[cfg(not(feature = "std"))]
+const fn parse_page_size(s: &str) -> usize {
+    let bytes = s.as_bytes();
+    let mut res = 0usize;
+    let mut i = 0;
+    while i < bytes.len() {
+        assert!(
+            bytes[i].is_ascii_digit(),
+            "PINNED_DEQUE_PAGE_SIZE must be a decimal number"
+        );
+        res = res * 10 + (bytes[i] - b'0') as usize;
+        i += 1;
+    }
+    assert!(
+        res.is_power_of_two(),
+        "PINNED_DEQUE_PAGE_SIZE must be a power of 2"
+    );
+    res
+}
+
 impl<T: Sized> Chunk<T> {
     pub(crate) fn capacity_per_chunk() -> u32 {
-        let page_size = page_size::get();
+        let page_size = page_size();
         assert!(
             size_of::<Chunk<T>>() + size_of::<T>() <= page_size,
             "size of a single element: {}, size of the overhead: {}",
@@ -22,23 +62,67 @@ impl<T: Sized> Chunk<T> {
         res as u32
     }
 
+    /// A chunk is padded to a multiple of the page size and aligned to pages,
+    /// so that its physical memory can be released as a whole,
+    /// only if the padding is at most 1/8 of the chunk.
+    /// Otherwise, a chunk of a page or larger is aligned to pages but not padded,
+    /// so all its pages but the last one can be released,
+    /// and a smaller chunk is aligned only as its header requires.
     pub(crate) fn layout(cap_per_chunk: u32) -> Layout {
         assert!(cap_per_chunk < u32::MAX);
-        let page_size = page_size::get();
+        let page_size = page_size();
         let chunk_size = size_of::<Chunk<T>>() + size_of::<T>() * (cap_per_chunk as usize);
-        Layout::from_size_align(chunk_size, page_size).unwrap()
+        let padded_size = chunk_size.next_multiple_of(page_size);
+        if padded_size - chunk_size <= chunk_size / 8 {
+            Layout::from_size_align(padded_size, page_size).unwrap()
+        } else if chunk_size >= page_size {
+            Layout::from_size_align(chunk_size, page_size).unwrap()
+        } else {
+            Layout::from_size_align(chunk_size, align_of::<Chunk<T>>()).unwrap()
+        }
+    }
+
+    /// Returns how many bytes of a chunk lie in whole pages, which can be released.
+    pub(crate) fn releasable_size(layout: Layout) -> usize {
+        let page_size = page_size();
+        if layout.align() < page_size {
+            0
+        } else {
+            layout.size() / page_size * page_size
+        }
     }
 
-    pub(crate) fn new(layout: Layout) -> *mut Self {
-        unsafe { alloc_zeroed(layout) as *mut Self }
+    pub(crate) fn try_new(layout: Layout) -> Result<*mut Self, TryReserveError> {
+        let res = unsafe { alloc_zeroed(layout) as *mut Self };
+        if res.is_null() {
+            Err(TryReserveError::new(layout))
+        } else {
+            Ok(res)
+        }
     }
 
     pub(crate) fn free(ptr: *mut Self, layout: Layout) {
         unsafe {
-            std::alloc::dealloc(ptr as *mut u8, layout);
+            dealloc(ptr as *mut u8, layout);
+        }
+    }
+
+    /// Releases physical memory of a chunk, but keeps it mapped.
+    ///
+    /// Its header must be reset before the chunk is used again.
+    #[cfg(all(unix, feature = "std"))]
+    pub(crate) fn release_memory(ptr: *mut Self, layout: Layout) {
+        let size = Self::releasable_size(layout);
+        if size > 0 {
+            unsafe {
+                libc::madvise(ptr as *mut libc::c_void, size, libc::MADV_DONTNEED);
+            }
         }
     }
 
+    #[cfg(not(all(unix, feature = "std")))]
+    pub(crate) fn release_memory(_: *mut Self, _: Layout) {}
+
     pub(crate) fn reset_for_front_insertion(&mut self, cap_per_chunk: u32) {
         self.end = cap_per_chunk;
         self.start = cap_per_chunk;
@@ -69,20 +153,39 @@ impl<T: Sized> Chunk<T> {
         }
     }
 
+    /// Returns the slot which `reserve_front()` would reserve, without reserving it.
+    pub(crate) fn spare_front(&mut self) -> Option<&mut MaybeUninit<T>> {
+        if self.start > 0 {
+            Some(self.inner_get_mut(self.start - 1))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the slot which `reserve_back()` would reserve, without reserving it.
+    pub(crate) fn spare_back(&mut self, cap_per_chunk: u32) -> Option<&mut MaybeUninit<T>> {
+        if self.end < cap_per_chunk {
+            Some(self.inner_get_mut(self.end))
+        } else {
+            None
+        }
+    }
+
     pub(crate) fn len(&self) -> usize {
         let res = self.end - self.start;
         res as usize
     }
 
-    pub(crate) fn get(&self, idx: usize) -> &T {
-        debug_assert!(idx < self.len());
-        let res = self.inner_get(self.start.wrapping_add(idx as u32));
+    /// `slot` counts from the beginning of the chunk, rather than `start`.
+    pub(crate) fn slot(&self, slot: usize) -> &T {
+        debug_assert!(self.start as usize <= slot && slot < self.end as usize);
+        let res = self.inner_get(slot as u32);
         unsafe { res.assume_init_ref() }
     }
 
-    pub(crate) fn get_mut(&mut self, idx: usize) -> &mut T {
-        debug_assert!(idx < self.len());
-        let res = self.inner_get_mut(self.start.wrapping_add(idx as u32));
+    pub(crate) fn slot_mut(&mut self, slot: usize) -> &mut T {
+        debug_assert!(self.start as usize <= slot && slot < self.end as usize);
+        let res = self.inner_get_mut(slot as u32);
         unsafe { res.assume_init_mut() }
     }
 
@@ -124,6 +227,31 @@ impl<T: Sized> Chunk<T> {
         unsafe { res.assume_init_read() }
     }
 
+    /// Forgets the front element without reading it.
+    pub(crate) fn discard_front(&mut self) {
+        debug_assert!(self.start < self.end);
+        self.start = self.start.wrapping_add(1);
+    }
+
+    /// Forgets the back element without reading it.
+    pub(crate) fn discard_back(&mut self) {
+        debug_assert!(self.start < self.end);
+        self.end = self.end.wrapping_sub(1);
+    }
+
+    /// Returns elements in the chunk.
+    pub(crate) fn as_slice(&self) -> &[T] {
+        unsafe {
+            let elems = Chunk::elems(self).add(self.start as usize);
+            slice::from_raw_parts(elems, self.len())
+        }
+    }
+
+    /// Returns the pointer to the first slot of a chunk, without referring to its header.
+    pub(crate) fn elems(this: *const Self) -> *const T {
+        (this as *const u8).wrapping_add(size_of::<Self>()) as *const T
+    }
+
     pub(crate) fn drop_all(&mut self) {
         debug_assert!(self.start <= self.end);
         let mut ptr: *mut _ = self.inner_get_mut(self.start);
@@ -1,5 +1,6 @@
 use crate::*;
-use std::cmp::*;
+use alloc::{collections::VecDeque, vec::Vec};
+use core::cmp::*;
 
 impl<T> PartialOrd for PinnedDeque<T>
 where
@@ -9,23 +10,18 @@ where
         let mut it_a = self.iter();
         let mut it_b = other.iter();
         loop {
-            let Some(a) = it_a.next() else {
-                break;
-            };
-            let Some(b) = it_b.next() else {
-                break;
-            };
-            let cmp = a.partial_cmp(b);
-            if !matches!(cmp, Some(Ordering::Equal)) {
-                return cmp;
+            match (it_a.next(), it_b.next()) {
+                (Some(a), Some(b)) => {
+                    let cmp = a.partial_cmp(b);
+                    if !matches!(cmp, Some(Ordering::Equal)) {
+                        return cmp;
+                    }
+                }
+                (Some(_), None) => return Some(Ordering::Greater),
+                (None, Some(_)) => return Some(Ordering::Less),
+                (None, None) => return Some(Ordering::Equal),
             }
         }
-        match (it_a.next(), it_b.next()) {
-            (Some(_), None) => Some(Ordering::Greater),
-            (None, Some(_)) => Some(Ordering::Less),
-            (None, None) => Some(Ordering::Equal),
-            _ => unreachable!(),
-        }
     }
 }
 
@@ -37,17 +33,10 @@ where
         if self.len() != other.len() {
             return false;
         }
-        let mut it_a = self.iter();
-        let mut it_b = other.iter();
-        loop {
-            let Some(a) = it_a.next() else {
-                break;
-            };
-            let Some(b) = it_b.next() else {
-                break;
-            };
-            let eq = a.eq(b);
-            if !eq {
+        let it_a = self.iter();
+        let it_b = other.iter();
+        for (a, b) in it_a.zip(it_b) {
+            if !a.eq(b) {
                 return false;
             }
         }
@@ -65,3 +54,91 @@ where
 }
 
 impl<T> Eq for PinnedDeque<T> where T: Sized + Eq {}
+
+impl<T> PinnedDeque<T>
+where
+    T: Sized,
+{
+    /// Compares elements with `other` chunk by chunk.
+    fn eq_slice<U>(&self, other: &[U]) -> bool
+    where
+        T: PartialEq<U>,
+    {
+        if self.len() != other.len() {
+            return false;
+        }
+        let mut other = other;
+        for chunk in self.used.iter() {
+            let chunk = unsafe { &**chunk }.as_slice();
+            let (head, tail) = other.split_at(chunk.len());
+            if chunk != head {
+                return false;
+            }
+            other = tail;
+        }
+        true
+    }
+}
+
+impl<T, U> PartialEq<[U]> for PinnedDeque<T>
+where
+    T: Sized + PartialEq<U>,
+{
+    fn eq(&self, other: &[U]) -> bool {
+        self.eq_slice(other)
+    }
+}
+
+impl<T, U> PartialEq<&[U]> for PinnedDeque<T>
+where
+    T: Sized + PartialEq<U>,
+{
+    fn eq(&self, other: &&[U]) -> bool {
+        self.eq_slice(other)
+    }
+}
+
+impl<T, U> PartialEq<&mut [U]> for PinnedDeque<T>
+where
+    T: Sized + PartialEq<U>,
+{
+    fn eq(&self, other: &&mut [U]) -> bool {
+        self.eq_slice(other)
+    }
+}
+
+impl<T, U, const N: usize> PartialEq<[U; N]> for PinnedDeque<T>
+where
+    T: Sized + PartialEq<U>,
+{
+    fn eq(&self, other: &[U; N]) -> bool {
+        self.eq_slice(other)
+    }
+}
+
+impl<T, U, const N: usize> PartialEq<&[U; N]> for PinnedDeque<T>
+where
+    T: Sized + PartialEq<U>,
+{
+    fn eq(&self, other: &&[U; N]) -> bool {
+        self.eq_slice(*other)
+    }
+}
+
+impl<T, U> PartialEq<Vec<U>> for PinnedDeque<T>
+where
+    T: Sized + PartialEq<U>,
+{
+    fn eq(&self, other: &Vec<U>) -> bool {
+        self.eq_slice(other)
+    }
+}
+
+impl<T, U> PartialEq<VecDeque<U>> for PinnedDeque<T>
+where
+    T: Sized + PartialEq<U>,
+{
+    fn eq(&self, other: &VecDeque<U>) -> bool {
+        self.len() == other.len() && self.iter().zip(other.iter()).all(|(a, b)| a == b)
+    }
+}
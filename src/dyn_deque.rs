@@ -0,0 +1,438 @@
+use crate::chunk::page_size;
+use alloc::{
+    alloc::{alloc, dealloc, handle_alloc_error, Layout},
+    collections::{vec_deque, VecDeque},
+    vec::Vec,
+};
+use core::{fmt, iter::FusedIterator, marker::PhantomData, mem, pin::Pin, ptr};
+
+/// A deque of dynamically sized values, e.g., strings, slices and trait objects.
+///
+/// Values are packed into chunks as records, each of which is tagged by a pointer to its value.
+/// The pointer carries the length of a string or a slice, or the vtable of a trait object.
+/// A record which does not fit the rest of the back chunk starts a new chunk.
+///
+/// Like [`PinnedDeque`](crate::PinnedDeque), values never move until they are popped.
+/// Popped values are dropped in place.
+pub struct PinnedDynDeque<U: ?Sized> {
+    size: usize,
+    chunk_layout: Layout,
+    chunks: VecDeque<DynChunk>,
+    /// Spare chunks of `chunk_layout`.
+    /// Chunks dedicated to records larger than that are deallocated once they are empty.
+    freed: Vec<DynChunk>,
+    _marker: PhantomData<U>,
+}
+
+struct DynChunk {
+    ptr: *mut u8,
+    layout: Layout,
+    /// Offset of the first record.
+    start: usize,
+    /// Offset past the last record.
+    end: usize,
+    /// Offset of the last record.
+    last: usize,
+}
+
+/// The beginning of a record, followed by its value.
+struct Header<U: ?Sized> {
+    value: *mut U,
+    /// Offset of the previous record in the same chunk.
+    prev: usize,
+    /// Offset of the next record in the same chunk, or past this record if it is the last.
+    next: usize,
+}
+
+impl DynChunk {
+    fn new(layout: Layout) -> Self {
+        let ptr = unsafe { alloc(layout) };
+        if ptr.is_null() {
+            handle_alloc_error(layout);
+        }
+        Self {
+            ptr,
+            layout,
+            start: 0,
+            end: 0,
+            last: 0,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// Returns the offset where a record of `layout` would start, if it fits.
+    fn fit(&self, layout: Layout) -> Option<usize> {
+        if layout.align() > self.layout.align() {
+            return None;
+        }
+        let offset = self.end.checked_add(layout.align() - 1)? & !(layout.align() - 1);
+        if offset.checked_add(layout.size())? <= self.layout.size() {
+            Some(offset)
+        } else {
+            None
+        }
+    }
+
+    fn header<U: ?Sized>(&self, offset: usize) -> &Header<U> {
+        unsafe { &*(self.ptr.add(offset) as *const Header<U>) }
+    }
+}
+
+/// A chunk fetched for a record, which is recycled if writing the record panics.
+struct FetchedChunk<'a, U: ?Sized> {
+    deque: &'a mut PinnedDynDeque<U>,
+    chunk: Option<DynChunk>,
+}
+
+impl<U: ?Sized> Drop for FetchedChunk<'_, U> {
+    fn drop(&mut self) {
+        if let Some(chunk) = self.chunk.take() {
+            self.deque.recycle(chunk);
+        }
+    }
+}
+
+impl<U: ?Sized> PinnedDynDeque<U> {
+    /// Creates an empty deque whose chunks are memory pages.
+    pub fn new() -> Self {
+        Self::with_chunk_size(page_size())
+    }
+
+    /// Creates an empty deque whose chunks hold `chunk_size` bytes, rounded up to whole pages.
+    pub fn with_chunk_size(chunk_size: usize) -> Self {
+        let chunk_layout = Layout::from_size_align(chunk_size.max(1), page_size())
+            .unwrap()
+            .pad_to_align();
+        Self {
+            size: 0,
+            chunk_layout,
+            chunks: VecDeque::new(),
+            freed: Vec::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Pushes a value at the back, and returns it pinned.
+    ///
+    /// `coerce` turns a reference to the value into one to `U`, e.g., `|x| x`,
+    /// and it must return its argument, with the same size.
+    pub fn push_back_unsize<T>(
+        &mut self,
+        value: T,
+        coerce: impl FnOnce(&mut T) -> &mut U,
+    ) -> Pin<&mut U> {
+        let res = self.push_back_raw(Layout::new::<T>(), |slot| unsafe {
+            let slot = slot as *mut T;
+            slot.write(value);
+            let res: *mut U = coerce(&mut *slot);
+            if res as *mut u8 != slot as *mut u8 || mem::size_of_val(&*res) != mem::size_of::<T>() {
+                ptr::drop_in_place(slot);
+                panic!("`coerce` must return its argument");
+            }
+            res
+        });
+        unsafe { Pin::new_unchecked(&mut *res) }
+    }
+
+    pub fn pop_front(&mut self) -> bool {
+        let Some(front_chunk) = self.chunks.front_mut() else {
+            return false;
+        };
+        let header = front_chunk.header::<U>(front_chunk.start);
+        let (value, next) = (header.value, header.next);
+        front_chunk.start = next;
+        self.size -= 1;
+        let emptied = if front_chunk.is_empty() {
+            self.chunks.pop_front()
+        } else {
+            None
+        };
+        unsafe { ptr::drop_in_place(value) };
+        if let Some(chunk) = emptied {
+            self.recycle(chunk);
+        }
+        true
+    }
+
+    pub fn pop_back(&mut self) -> bool {
+        let Some(back_chunk) = self.chunks.back_mut() else {
+            return false;
+        };
+        let header = back_chunk.header::<U>(back_chunk.last);
+        let (value, prev) = (header.value, header.prev);
+        back_chunk.end = back_chunk.last;
+        back_chunk.last = prev;
+        self.size -= 1;
+        let emptied = if back_chunk.is_empty() {
+            self.chunks.pop_back()
+        } else {
+            None
+        };
+        unsafe { ptr::drop_in_place(value) };
+        if let Some(chunk) = emptied {
+            self.recycle(chunk);
+        }
+        true
+    }
+
+    pub fn front(&self) -> Option<&U> {
+        let front_chunk = self.chunks.front()?;
+        Some(unsafe { &*front_chunk.header::<U>(front_chunk.start).value })
+    }
+
+    pub fn back(&self) -> Option<&U> {
+        let back_chunk = self.chunks.back()?;
+        Some(unsafe { &*back_chunk.header::<U>(back_chunk.last).value })
+    }
+
+    pub fn front_mut(&mut self) -> Option<Pin<&mut U>> {
+        let front_chunk = self.chunks.front()?;
+        Some(unsafe { Pin::new_unchecked(&mut *front_chunk.header::<U>(front_chunk.start).value) })
+    }
+
+    pub fn back_mut(&mut self) -> Option<Pin<&mut U>> {
+        let back_chunk = self.chunks.back()?;
+        Some(unsafe { Pin::new_unchecked(&mut *back_chunk.header::<U>(back_chunk.last).value) })
+    }
+
+    pub fn clear(&mut self) {
+        while self.pop_front() {}
+    }
+
+    pub fn iter(&self) -> DynIter<'_, U> {
+        DynIter {
+            len: self.size,
+            chunks: self.chunks.iter(),
+            front: None,
+            back: None,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Appends a record of a value of `layout`.
+    ///
+    /// `write` initializes the value in the given slot, and returns the pointer to it as `U`.
+    /// If `write` panics, nothing is appended.
+    fn push_back_raw(&mut self, layout: Layout, write: impl FnOnce(*mut u8) -> *mut U) -> *mut U {
+        let (record_layout, value_offset) = Layout::new::<Header<U>>().extend(layout).unwrap();
+        let fit = self
+            .chunks
+            .back()
+            .and_then(|back_chunk| back_chunk.fit(record_layout));
+        let (mut chunk, offset) = match fit {
+            Some(offset) => (None, offset),
+            None => {
+                let chunk = self.fetch_a_chunk(record_layout);
+                (Some(chunk), 0)
+            }
+        };
+        let chunk_ptr = match chunk.as_ref() {
+            Some(chunk) => chunk.ptr,
+            None => unsafe { self.chunks.back().unwrap_unchecked() }.ptr,
+        };
+        let value = {
+            let mut fetched = FetchedChunk { deque: self, chunk };
+            let value = write(unsafe { chunk_ptr.add(offset + value_offset) });
+            chunk = fetched.chunk.take();
+            value
+        };
+        let back_chunk = match chunk.as_mut() {
+            Some(chunk) => chunk,
+            None => unsafe { self.chunks.back_mut().unwrap_unchecked() },
+        };
+        let next = offset + record_layout.size();
+        unsafe {
+            (back_chunk.ptr.add(offset) as *mut Header<U>).write(Header {
+                value,
+                prev: back_chunk.last,
+                next,
+            });
+        }
+        if back_chunk.is_empty() {
+            back_chunk.start = offset;
+        } else {
+            // The previous record ends before the padding, but it points to this record.
+            unsafe { (*(back_chunk.ptr.add(back_chunk.last) as *mut Header<U>)).next = offset };
+        }
+        back_chunk.last = offset;
+        back_chunk.end = next;
+        if let Some(chunk) = chunk {
+            self.chunks.push_back(chunk);
+        }
+        self.size += 1;
+        value
+    }
+
+    /// Returns an empty chunk large enough for a record of `record_layout`.
+    fn fetch_a_chunk(&mut self, record_layout: Layout) -> DynChunk {
+        if record_layout.align() <= self.chunk_layout.align()
+            && record_layout.size() <= self.chunk_layout.size()
+        {
+            self.freed
+                .pop()
+                .unwrap_or_else(|| DynChunk::new(self.chunk_layout))
+        } else {
+            let align = record_layout.align().max(self.chunk_layout.align());
+            let layout = Layout::from_size_align(record_layout.size(), align)
+                .unwrap()
+                .pad_to_align();
+            DynChunk::new(layout)
+        }
+    }
+
+    fn recycle(&mut self, mut chunk: DynChunk) {
+        if chunk.layout == self.chunk_layout {
+            chunk.start = 0;
+            chunk.end = 0;
+            chunk.last = 0;
+            self.freed.push(chunk);
+        } else {
+            unsafe { dealloc(chunk.ptr, chunk.layout) };
+        }
+    }
+}
+
+impl PinnedDynDeque<str> {
+    pub fn push_back_str(&mut self, s: &str) -> &mut str {
+        let res = self.push_back_raw(Layout::for_value(s), |slot| unsafe {
+            ptr::copy_nonoverlapping(s.as_ptr(), slot, s.len());
+            ptr::slice_from_raw_parts_mut(slot, s.len()) as *mut str
+        });
+        unsafe { &mut *res }
+    }
+}
+
+impl<T: Copy> PinnedDynDeque<[T]> {
+    pub fn push_back_slice(&mut self, xs: &[T]) -> &mut [T] {
+        let res = self.push_back_raw(Layout::for_value(xs), |slot| unsafe {
+            let slot = slot as *mut T;
+            ptr::copy_nonoverlapping(xs.as_ptr(), slot, xs.len());
+            ptr::slice_from_raw_parts_mut(slot, xs.len())
+        });
+        unsafe { &mut *res }
+    }
+}
+
+impl<U: ?Sized> Default for PinnedDynDeque<U> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<U: ?Sized> Drop for PinnedDynDeque<U> {
+    fn drop(&mut self) {
+        self.clear();
+        for chunk in self.freed.drain(..) {
+            unsafe { dealloc(chunk.ptr, chunk.layout) };
+        }
+    }
+}
+
+impl<U: ?Sized + fmt::Debug> fmt::Debug for PinnedDynDeque<U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, U: ?Sized> IntoIterator for &'a PinnedDynDeque<U> {
+    type Item = &'a U;
+    type IntoIter = DynIter<'a, U>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+pub struct DynIter<'a, U: ?Sized> {
+    len: usize,
+    chunks: vec_deque::Iter<'a, DynChunk>,
+    /// The chunk and the offset of the next record from the front.
+    front: Option<(&'a DynChunk, usize)>,
+    /// The chunk and the offset of the next record from the back.
+    back: Option<(&'a DynChunk, Option<usize>)>,
+    _marker: PhantomData<&'a U>,
+}
+
+impl<U: ?Sized> Clone for DynIter<'_, U> {
+    fn clone(&self) -> Self {
+        Self {
+            len: self.len,
+            chunks: self.chunks.clone(),
+            front: self.front,
+            back: self.back,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, U: ?Sized> Iterator for DynIter<'a, U> {
+    type Item = &'a U;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        loop {
+            if let Some((chunk, offset)) = self.front {
+                if offset < chunk.end {
+                    let header = chunk.header::<U>(offset);
+                    self.front = Some((chunk, header.next));
+                    self.len -= 1;
+                    return Some(unsafe { &*header.value });
+                }
+            }
+            // The rest are in the chunk which the back goes through, if no other chunks remain.
+            self.front = self
+                .chunks
+                .next()
+                .or_else(|| self.back.map(|(chunk, _)| chunk))
+                .map(|chunk| (chunk, chunk.start));
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<U: ?Sized> DoubleEndedIterator for DynIter<'_, U> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        loop {
+            if let Some((chunk, Some(offset))) = self.back {
+                let header = chunk.header::<U>(offset);
+                let prev = if offset == chunk.start {
+                    None
+                } else {
+                    Some(header.prev)
+                };
+                self.back = Some((chunk, prev));
+                self.len -= 1;
+                return Some(unsafe { &*header.value });
+            }
+            // The rest are in the chunk which the front goes through, if no other chunks remain.
+            self.back = self
+                .chunks
+                .next_back()
+                .or_else(|| self.front.map(|(chunk, _)| chunk))
+                .map(|chunk| (chunk, Some(chunk.last)));
+        }
+    }
+}
+
+impl<U: ?Sized> ExactSizeIterator for DynIter<'_, U> {}
+
+impl<U: ?Sized> FusedIterator for DynIter<'_, U> {}
@@ -0,0 +1,286 @@
+use crate::{chunk::Chunk, *};
+use core::{
+    mem::{ManuallyDrop, MaybeUninit},
+    ptr,
+};
+
+impl<T> PinnedDeque<T>
+where
+    T: Sized,
+{
+    /// Pushes an element built by `f` at the back.
+    ///
+    /// The element is written into its slot directly, rather than built on the stack and moved.
+    /// If the deque is full, it follows the overflow policy as `push_back()` does,
+    /// and an element evicted is put back if `f` panics.
+    pub fn push_back_with(&mut self, f: impl FnOnce() -> T) {
+        match self.reserve_back_slot() {
+            Some(slot) => {
+                slot.write(f());
+            }
+            // The element rejected is dropped.
+            None => drop(f()),
+        }
+    }
+
+    /// Pushes an element built by `f` at the front.
+    ///
+    /// The element is written into its slot directly, rather than built on the stack and moved.
+    /// If the deque is full, it follows the overflow policy as `push_front()` does,
+    /// and an element evicted is put back if `f` panics.
+    pub fn push_front_with(&mut self, f: impl FnOnce() -> T) {
+        match self.reserve_front_slot() {
+            Some(slot) => {
+                slot.write(f());
+            }
+            // The element rejected is dropped.
+            None => drop(f()),
+        }
+    }
+
+    /// Returns the slot which the next element pushed at the back will occupy.
+    ///
+    /// The element is not a part of the deque until `commit_back_uninit()`.
+    /// If the deque is bounded and full, room is made by the overflow policy first.
+    pub fn push_back_uninit(&mut self) -> &mut MaybeUninit<T> {
+        self.make_room();
+        self.spare_back_slot()
+    }
+
+    /// Returns the slot which the next element pushed at the front will occupy.
+    ///
+    /// The element is not a part of the deque until `commit_front_uninit()`.
+    /// If the deque is bounded and full, room is made by the overflow policy first.
+    pub fn push_front_uninit(&mut self) -> &mut MaybeUninit<T> {
+        self.make_room();
+        self.spare_front_slot()
+    }
+
+    fn spare_back_slot(&mut self) -> &mut MaybeUninit<T> {
+        self.unshare_back_for_push();
+        if let Some(back_chunk) = self.used.back() {
+            let back_chunk = unsafe { &mut **back_chunk };
+            if back_chunk.end < self.cap_per_chunk {
+                return unsafe { back_chunk.spare_back(self.cap_per_chunk).unwrap_unchecked() };
+            }
+        }
+        let spare_chunk = unsafe { &mut *self.spare_chunk() };
+        spare_chunk.reset_for_back_insertion();
+        unsafe {
+            spare_chunk
+                .spare_back(self.cap_per_chunk)
+                .unwrap_unchecked()
+        }
+    }
+
+    fn spare_front_slot(&mut self) -> &mut MaybeUninit<T> {
+        self.unshare_front_for_push();
+        if let Some(front_chunk) = self.used.front() {
+            let front_chunk = unsafe { &mut **front_chunk };
+            if front_chunk.start > 0 {
+                return unsafe { front_chunk.spare_front().unwrap_unchecked() };
+            }
+        }
+        let spare_chunk = unsafe { &mut *self.spare_chunk() };
+        spare_chunk.reset_for_front_insertion(self.cap_per_chunk);
+        unsafe { spare_chunk.spare_front().unwrap_unchecked() }
+    }
+
+    /// Makes the slot returned by `push_back_uninit()` the back element.
+    ///
+    /// If the deque is still full, e.g., under [`OverflowPolicy::Reject`],
+    /// the element is returned instead, as `push_back_bounded()` does.
+    ///
+    /// # Safety
+    ///
+    /// The slot must be initialized,
+    /// and the deque must not be modified since `push_back_uninit()`.
+    pub unsafe fn commit_back_uninit(&mut self) -> Option<T> {
+        if self.is_full() {
+            return Some(ptr::read(self.spare_back_slot().as_ptr()));
+        }
+        if let Some(back_chunk) = self.used.back() {
+            let back_chunk = &mut **back_chunk;
+            if back_chunk.reserve_back(self.cap_per_chunk).is_some() {
+                self.size += 1;
+                return None;
+            }
+        }
+        let new_chunk = self.fetch_a_freed_chunk();
+        (*new_chunk).reset_for_back_insertion();
+        (*new_chunk)
+            .reserve_back(self.cap_per_chunk)
+            .unwrap_unchecked();
+        self.attach_back(new_chunk);
+        self.size += 1;
+        None
+    }
+
+    /// Makes the slot returned by `push_front_uninit()` the front element.
+    ///
+    /// If the deque is still full, e.g., under [`OverflowPolicy::Reject`],
+    /// the element is returned instead, as `push_front_bounded()` does.
+    ///
+    /// # Safety
+    ///
+    /// The slot must be initialized,
+    /// and the deque must not be modified since `push_front_uninit()`.
+    pub unsafe fn commit_front_uninit(&mut self) -> Option<T> {
+        if self.is_full() {
+            return Some(ptr::read(self.spare_front_slot().as_ptr()));
+        }
+        self.head_seq = self.head_seq.wrapping_sub(1);
+        self.size += 1;
+        if let Some(front_chunk) = self.used.front() {
+            let front_chunk = &mut **front_chunk;
+            if front_chunk.reserve_front().is_some() {
+                return None;
+            }
+        }
+        let new_chunk = self.fetch_a_freed_chunk();
+        (*new_chunk).reset_for_front_insertion(self.cap_per_chunk);
+        (*new_chunk).reserve_front().unwrap_unchecked();
+        self.attach_front(new_chunk);
+        None
+    }
+
+    /// Evicts an element if the deque is full, following the overflow policy.
+    fn make_room(&mut self) {
+        drop(self.evict());
+    }
+
+    /// Like `make_room()`, but returns the element evicted.
+    fn evict(&mut self) -> Option<T> {
+        if !self.is_full() {
+            return None;
+        }
+        match self.overflow_policy {
+            OverflowPolicy::Reject => None,
+            OverflowPolicy::EvictFront => self.pop_front(),
+            OverflowPolicy::EvictBack => self.pop_back(),
+            OverflowPolicy::Panic => panic!("the deque is full (max_len: {})", self.max_len),
+        }
+    }
+
+    /// Returns the top of `freed`, allocating it if there is none.
+    pub(crate) fn spare_chunk(&mut self) -> *mut Chunk<T> {
+        if let Some(chunk) = self.freed.last() {
+            *chunk
+        } else {
+            let chunk = self.fetch_a_freed_chunk();
+            self.freed.push(chunk);
+            chunk
+        }
+    }
+}
+
+impl<T> PinnedDeque<T>
+where
+    T: Sized,
+{
+    /// Reserves the slot which the next element pushed at the back will occupy.
+    ///
+    /// Returns `None` if the deque is bounded and full, and the overflow policy makes no room.
+    /// If an element is evicted to make room, it is put back unless the slot is committed.
+    pub fn reserve_back_slot(&mut self) -> Option<Slot<'_, T>> {
+        let evicted = self.evict();
+        if self.is_full() {
+            return None;
+        }
+        let slot = self.spare_back_slot().as_mut_ptr();
+        Some(Slot {
+            deque: self,
+            slot,
+            back: true,
+            evicted,
+        })
+    }
+
+    /// Reserves the slot which the next element pushed at the front will occupy.
+    ///
+    /// Returns `None` if the deque is bounded and full, and the overflow policy makes no room.
+    /// If an element is evicted to make room, it is put back unless the slot is committed.
+    pub fn reserve_front_slot(&mut self) -> Option<Slot<'_, T>> {
+        let evicted = self.evict();
+        if self.is_full() {
+            return None;
+        }
+        let slot = self.spare_front_slot().as_mut_ptr();
+        Some(Slot {
+            deque: self,
+            slot,
+            back: false,
+            evicted,
+        })
+    }
+}
+
+/// A slot reserved at either end of a deque.
+///
+/// The slot is already at its final address, but it is not a part of the deque until committed.
+/// Dropping the guard without committing gives the slot back,
+/// and puts back the element evicted on reserving it, if any.
+/// Nothing is counted in the deque before the commit, so leaking the guard is safe as well,
+/// though the element evicted is leaked then.
+pub struct Slot<'a, T: Sized> {
+    deque: &'a mut PinnedDeque<T>,
+    slot: *mut T,
+    back: bool,
+    /// The element evicted to make room, which is dropped on the commit.
+    evicted: Option<T>,
+}
+
+impl<'a, T: Sized> Slot<'a, T> {
+    pub fn as_ptr(&self) -> *const T {
+        self.slot
+    }
+
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.slot
+    }
+
+    pub fn as_uninit_mut(&mut self) -> &mut MaybeUninit<T> {
+        unsafe { &mut *(self.slot as *mut MaybeUninit<T>) }
+    }
+
+    /// Writes the element into the slot and commits it.
+    pub fn write(self, elem: T) -> &'a mut T {
+        unsafe {
+            self.slot.write(elem);
+            self.assume_init()
+        }
+    }
+
+    /// Commits the slot, which is initialized through `as_mut_ptr()` or `as_uninit_mut()`.
+    ///
+    /// # Safety
+    ///
+    /// The slot must be initialized.
+    pub unsafe fn assume_init(self) -> &'a mut T {
+        let mut this = ManuallyDrop::new(self);
+        let evicted = this.evicted.take();
+        let deque = ptr::read(&this.deque);
+        // The slot is reserved only if the deque is not full.
+        if this.back {
+            deque.commit_back_uninit();
+        } else {
+            deque.commit_front_uninit();
+        }
+        drop(evicted);
+        &mut *this.slot
+    }
+}
+
+impl<T: Sized> Drop for Slot<'_, T> {
+    fn drop(&mut self) {
+        let Some(evicted) = self.evicted.take() else {
+            return;
+        };
+        // The slot is given back, so the deque is not full any more.
+        match self.deque.overflow_policy {
+            OverflowPolicy::EvictFront => self.deque.push_front_unbounded(evicted),
+            OverflowPolicy::EvictBack => self.deque.push_back_unbounded(evicted),
+            OverflowPolicy::Reject | OverflowPolicy::Panic => unreachable!(),
+        }
+    }
+}
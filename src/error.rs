@@ -0,0 +1,145 @@
+use alloc::{alloc::handle_alloc_error, collections};
+use core::{alloc::Layout, fmt};
+
+/// The error type for `try_reserve` methods.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TryReserveError {
+    kind: TryReserveErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TryReserveErrorKind {
+    /// Allocating a chunk failed.
+    Chunk(Layout),
+    /// Growing the list of chunks failed.
+    List(collections::TryReserveError),
+}
+
+impl TryReserveError {
+    pub(crate) fn new(layout: Layout) -> Self {
+        Self {
+            kind: TryReserveErrorKind::Chunk(layout),
+        }
+    }
+
+    /// Returns the layout of the chunk which failed to be allocated,
+    /// or `None` if growing the list of chunks failed.
+    pub fn layout(&self) -> Option<Layout> {
+        match &self.kind {
+            TryReserveErrorKind::Chunk(layout) => Some(*layout),
+            TryReserveErrorKind::List(_) => None,
+        }
+    }
+
+    /// Aborts or panics as the infallible counterparts of `try_` methods do.
+    pub(crate) fn handle(self) -> ! {
+        match self.kind {
+            TryReserveErrorKind::Chunk(layout) => handle_alloc_error(layout),
+            TryReserveErrorKind::List(err) => panic!("{err}"),
+        }
+    }
+}
+
+impl From<collections::TryReserveError> for TryReserveError {
+    fn from(err: collections::TryReserveError) -> Self {
+        Self {
+            kind: TryReserveErrorKind::List(err),
+        }
+    }
+}
+
+impl fmt::Display for TryReserveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            TryReserveErrorKind::Chunk(layout) => {
+                write!(f, "memory allocation of {} bytes failed", layout.size())
+            }
+            TryReserveErrorKind::List(err) => fmt::Display::fmt(err, f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TryReserveError {}
+
+/// The error type for `try_push_*` methods.
+///
+/// It gives back the element which failed to be pushed.
+pub struct TryPushError<T> {
+    elem: T,
+    kind: TryPushErrorKind,
+}
+
+#[derive(Debug)]
+enum TryPushErrorKind {
+    /// Memory allocation failed.
+    Alloc(TryReserveError),
+    /// A bounded deque is full and rejects elements.
+    Full,
+}
+
+impl<T> TryPushError<T> {
+    pub(crate) fn new(elem: T, error: TryReserveError) -> Self {
+        Self {
+            elem,
+            kind: TryPushErrorKind::Alloc(error),
+        }
+    }
+
+    pub(crate) fn full(elem: T) -> Self {
+        Self {
+            elem,
+            kind: TryPushErrorKind::Full,
+        }
+    }
+
+    pub fn element(&self) -> &T {
+        &self.elem
+    }
+
+    pub fn into_element(self) -> T {
+        self.elem
+    }
+
+    /// Returns the allocation error, or `None` if the deque is full.
+    pub fn error(&self) -> Option<&TryReserveError> {
+        match &self.kind {
+            TryPushErrorKind::Alloc(error) => Some(error),
+            TryPushErrorKind::Full => None,
+        }
+    }
+
+    /// Returns whether the element is rejected by a full bounded deque
+    /// under [`OverflowPolicy::Reject`](crate::OverflowPolicy::Reject).
+    pub fn is_full(&self) -> bool {
+        matches!(self.kind, TryPushErrorKind::Full)
+    }
+
+    /// Aborts or panics as the infallible counterparts of `try_` methods do.
+    pub(crate) fn handle(self) -> ! {
+        match self.kind {
+            TryPushErrorKind::Alloc(error) => error.handle(),
+            TryPushErrorKind::Full => panic!("the deque is full"),
+        }
+    }
+}
+
+impl<T> fmt::Debug for TryPushError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TryPushError")
+            .field("kind", &self.kind)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> fmt::Display for TryPushError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            TryPushErrorKind::Alloc(error) => fmt::Display::fmt(error, f),
+            TryPushErrorKind::Full => f.write_str("the deque is full"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> std::error::Error for TryPushError<T> {}
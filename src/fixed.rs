@@ -0,0 +1,501 @@
+use alloc::{
+    alloc::{alloc, dealloc, handle_alloc_error, Layout},
+    collections::{vec_deque, VecDeque},
+    vec::Vec,
+};
+use core::{fmt, iter::*, mem::MaybeUninit, ptr, slice};
+
+/// A chunk whose capacity is known at compile time.
+struct FixedChunk<T: Sized, const CAP: usize> {
+    start: u32,
+    end: u32,
+    elems: [MaybeUninit<T>; CAP],
+}
+
+impl<T: Sized, const CAP: usize> FixedChunk<T, CAP> {
+    const CHECK_CAP: () = assert!(CAP > 0 && CAP < u32::MAX as usize);
+
+    fn allocate() -> *mut Self {
+        let layout = Layout::new::<Self>();
+        let res = unsafe { alloc(layout) as *mut Self };
+        if res.is_null() {
+            handle_alloc_error(layout);
+        }
+        res
+    }
+
+    fn free(ptr: *mut Self) {
+        unsafe {
+            dealloc(ptr as *mut u8, Layout::new::<Self>());
+        }
+    }
+
+    fn reset_for_front_insertion(this: *mut Self) {
+        unsafe {
+            ptr::addr_of_mut!((*this).start).write(CAP as u32);
+            ptr::addr_of_mut!((*this).end).write(CAP as u32);
+        }
+    }
+
+    fn reset_for_back_insertion(this: *mut Self) {
+        unsafe {
+            ptr::addr_of_mut!((*this).start).write(0);
+            ptr::addr_of_mut!((*this).end).write(0);
+        }
+    }
+
+    fn len(&self) -> usize {
+        (self.end - self.start) as usize
+    }
+
+    fn as_slice(&self) -> &[T] {
+        let elems = &self.elems[self.start as usize..self.end as usize];
+        unsafe { &*(elems as *const [MaybeUninit<T>] as *const [T]) }
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        let elems = &mut self.elems[self.start as usize..self.end as usize];
+        unsafe { &mut *(elems as *mut [MaybeUninit<T>] as *mut [T]) }
+    }
+
+    fn reserve_front(&mut self) -> Option<&mut MaybeUninit<T>> {
+        if self.start > 0 {
+            self.start -= 1;
+            Some(&mut self.elems[self.start as usize])
+        } else {
+            None
+        }
+    }
+
+    fn reserve_back(&mut self) -> Option<&mut MaybeUninit<T>> {
+        if (self.end as usize) < CAP {
+            self.end += 1;
+            Some(&mut self.elems[self.end as usize - 1])
+        } else {
+            None
+        }
+    }
+
+    fn pop_front(&mut self) -> T {
+        debug_assert!(self.start < self.end);
+        self.start += 1;
+        unsafe { self.elems[self.start as usize - 1].assume_init_read() }
+    }
+
+    fn pop_back(&mut self) -> T {
+        debug_assert!(self.start < self.end);
+        self.end -= 1;
+        unsafe { self.elems[self.end as usize].assume_init_read() }
+    }
+
+    fn drop_all(&mut self) {
+        unsafe {
+            ptr::drop_in_place(self.as_mut_slice());
+        }
+        self.start = self.end;
+    }
+}
+
+/// A deque like [`PinnedDeque`](crate::PinnedDeque), whose capacity per chunk is known at compile time.
+///
+/// Thanks to the constant capacity, index arithmetic is cheaper,
+/// especially when `CAP` is a power of 2.
+pub struct FixedChunkDeque<T: Sized, const CAP: usize> {
+    size: usize,
+    used: VecDeque<*mut FixedChunk<T, CAP>>,
+    freed: Vec<*mut FixedChunk<T, CAP>>,
+}
+
+impl<T, const CAP: usize> FixedChunkDeque<T, CAP>
+where
+    T: Sized,
+{
+    pub fn new() -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = FixedChunk::<T, CAP>::CHECK_CAP;
+        Self {
+            size: 0,
+            used: VecDeque::new(),
+            freed: Vec::new(),
+        }
+    }
+
+    /// Reserves additional capacity in order to avoid memory allocations then.
+    pub fn reserve(&mut self, additional: usize) {
+        let n = additional.div_ceil(CAP);
+        if n > self.freed.len() {
+            for _ in self.freed.len()..n {
+                self.freed.push(FixedChunk::allocate());
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the total capacity of the deque.
+    pub fn capacity(&self) -> usize {
+        (self.used.len() + self.freed.len()) * CAP
+    }
+
+    pub fn push_back(&mut self, elem: T) {
+        self.size += 1;
+        if let Some(back_chunk) = self.used.back() {
+            let back_chunk = unsafe { &mut **back_chunk };
+            if let Some(slot) = back_chunk.reserve_back() {
+                slot.write(elem);
+                return;
+            }
+        }
+        let new_chunk = self.fetch_a_freed_chunk();
+        FixedChunk::reset_for_back_insertion(new_chunk);
+        unsafe {
+            (*new_chunk).reserve_back().unwrap_unchecked().write(elem);
+        }
+        self.used.push_back(new_chunk);
+    }
+
+    pub fn push_front(&mut self, elem: T) {
+        self.size += 1;
+        if let Some(front_chunk) = self.used.front() {
+            let front_chunk = unsafe { &mut **front_chunk };
+            if let Some(slot) = front_chunk.reserve_front() {
+                slot.write(elem);
+                return;
+            }
+        }
+        let new_chunk = self.fetch_a_freed_chunk();
+        FixedChunk::reset_for_front_insertion(new_chunk);
+        unsafe {
+            (*new_chunk).reserve_front().unwrap_unchecked().write(elem);
+        }
+        self.used.push_front(new_chunk);
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        let back_chunk = unsafe { &mut **self.used.back()? };
+        let res = back_chunk.pop_back();
+        if back_chunk.len() == 0 {
+            let last_chunk = unsafe { self.used.pop_back().unwrap_unchecked() };
+            self.freed.push(last_chunk);
+        }
+        self.size -= 1;
+        Some(res)
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        let front_chunk = unsafe { &mut **self.used.front()? };
+        let res = front_chunk.pop_front();
+        if front_chunk.len() == 0 {
+            let first_chunk = unsafe { self.used.pop_front().unwrap_unchecked() };
+            self.freed.push(first_chunk);
+        }
+        self.size -= 1;
+        Some(res)
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        self.used
+            .back()
+            .map(|chunk| unsafe { (**chunk).as_slice().last().unwrap_unchecked() })
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.used
+            .front()
+            .map(|chunk| unsafe { (**chunk).as_slice().first().unwrap_unchecked() })
+    }
+
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        self.used
+            .back()
+            .map(|chunk| unsafe { (**chunk).as_mut_slice().last_mut().unwrap_unchecked() })
+    }
+
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        self.used
+            .front()
+            .map(|chunk| unsafe { (**chunk).as_mut_slice().first_mut().unwrap_unchecked() })
+    }
+
+    pub fn clear(&mut self) {
+        while let Some(chunk_ptr) = self.used.pop_front() {
+            unsafe { &mut *chunk_ptr }.drop_all();
+            self.freed.push(chunk_ptr);
+        }
+        self.size = 0;
+    }
+
+    pub fn get(&self, idx: usize) -> Option<&T> {
+        let (chunk, offset) = self.locate(idx)?;
+        Some(unsafe { (*chunk).elems[offset].assume_init_ref() })
+    }
+
+    pub fn get_mut(&mut self, idx: usize) -> Option<&mut T> {
+        let (chunk, offset) = self.locate(idx)?;
+        Some(unsafe { (*chunk).elems[offset].assume_init_mut() })
+    }
+
+    pub fn iter(&self) -> FixedChunkIter<'_, T, CAP> {
+        FixedChunkIter {
+            size: self.size,
+            chunk_iter: self.used.iter(),
+            front: [].iter(),
+            back: [].iter(),
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> FixedChunkIterMut<'_, T, CAP> {
+        FixedChunkIterMut {
+            size: self.size,
+            chunk_iter: self.used.iter(),
+            front: [].iter_mut(),
+            back: [].iter_mut(),
+        }
+    }
+
+    /// All chunks but the first one start at 0, and all chunks but the last one end at `CAP`.
+    /// So, an element can be located as if the first chunk were full as well.
+    fn locate(&self, idx: usize) -> Option<(*mut FixedChunk<T, CAP>, usize)> {
+        if idx >= self.len() {
+            return None;
+        }
+        let first_chunk = unsafe { &**self.used.front().unwrap_unchecked() };
+        let idx = idx + first_chunk.start as usize;
+        let chunk = unsafe { *self.used.get(idx / CAP).unwrap_unchecked() };
+        Some((chunk, idx % CAP))
+    }
+
+    fn fetch_a_freed_chunk(&mut self) -> *mut FixedChunk<T, CAP> {
+        if let Some(chunk) = self.freed.pop() {
+            chunk
+        } else {
+            FixedChunk::allocate()
+        }
+    }
+}
+
+impl<T, const CAP: usize> Drop for FixedChunkDeque<T, CAP>
+where
+    T: Sized,
+{
+    fn drop(&mut self) {
+        self.clear();
+        while let Some(chunk) = self.freed.pop() {
+            FixedChunk::free(chunk);
+        }
+    }
+}
+
+impl<T, const CAP: usize> Default for FixedChunkDeque<T, CAP>
+where
+    T: Sized,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const CAP: usize> fmt::Debug for FixedChunkDeque<T, CAP>
+where
+    T: Sized + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T, const CAP: usize> Extend<T> for FixedChunkDeque<T, CAP>
+where
+    T: Sized,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        let (size_hint, _) = iter.size_hint();
+        self.reserve(size_hint);
+        for x in iter {
+            self.push_back(x);
+        }
+    }
+}
+
+impl<T, const CAP: usize> FromIterator<T> for FixedChunkDeque<T, CAP>
+where
+    T: Sized,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut res = Self::new();
+        res.extend(iter);
+        res
+    }
+}
+
+#[derive(Clone)]
+pub struct FixedChunkIter<'a, T: Sized, const CAP: usize> {
+    size: usize,
+    chunk_iter: vec_deque::Iter<'a, *mut FixedChunk<T, CAP>>,
+    front: slice::Iter<'a, T>,
+    back: slice::Iter<'a, T>,
+}
+
+pub struct FixedChunkIterMut<'a, T: Sized, const CAP: usize> {
+    size: usize,
+    chunk_iter: vec_deque::Iter<'a, *mut FixedChunk<T, CAP>>,
+    front: slice::IterMut<'a, T>,
+    back: slice::IterMut<'a, T>,
+}
+
+pub struct FixedChunkIntoIter<T: Sized, const CAP: usize>(FixedChunkDeque<T, CAP>);
+
+impl<'a, T, const CAP: usize> Iterator for FixedChunkIter<'a, T, CAP>
+where
+    T: Sized,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(res) = self.front.next() {
+                self.size -= 1;
+                return Some(res);
+            }
+            if let Some(chunk) = self.chunk_iter.next() {
+                self.front = unsafe { &**chunk }.as_slice().iter();
+            } else {
+                let res = self.back.next()?;
+                self.size -= 1;
+                return Some(res);
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.size, Some(self.size))
+    }
+}
+
+impl<T, const CAP: usize> DoubleEndedIterator for FixedChunkIter<'_, T, CAP>
+where
+    T: Sized,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(res) = self.back.next_back() {
+                self.size -= 1;
+                return Some(res);
+            }
+            if let Some(chunk) = self.chunk_iter.next_back() {
+                self.back = unsafe { &**chunk }.as_slice().iter();
+            } else {
+                let res = self.front.next_back()?;
+                self.size -= 1;
+                return Some(res);
+            }
+        }
+    }
+}
+
+impl<'a, T, const CAP: usize> Iterator for FixedChunkIterMut<'a, T, CAP>
+where
+    T: Sized,
+{
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(res) = self.front.next() {
+                self.size -= 1;
+                return Some(res);
+            }
+            if let Some(chunk) = self.chunk_iter.next() {
+                self.front = unsafe { &mut **chunk }.as_mut_slice().iter_mut();
+            } else {
+                let res = self.back.next()?;
+                self.size -= 1;
+                return Some(res);
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.size, Some(self.size))
+    }
+}
+
+impl<T, const CAP: usize> DoubleEndedIterator for FixedChunkIterMut<'_, T, CAP>
+where
+    T: Sized,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(res) = self.back.next_back() {
+                self.size -= 1;
+                return Some(res);
+            }
+            if let Some(chunk) = self.chunk_iter.next_back() {
+                self.back = unsafe { &mut **chunk }.as_mut_slice().iter_mut();
+            } else {
+                let res = self.front.next_back()?;
+                self.size -= 1;
+                return Some(res);
+            }
+        }
+    }
+}
+
+impl<T: Sized, const CAP: usize> Iterator for FixedChunkIntoIter<T, CAP> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let size = self.0.len();
+        (size, Some(size))
+    }
+}
+
+impl<T: Sized, const CAP: usize> DoubleEndedIterator for FixedChunkIntoIter<T, CAP> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.pop_back()
+    }
+}
+
+impl<T: Sized, const CAP: usize> ExactSizeIterator for FixedChunkIter<'_, T, CAP> {}
+
+impl<T: Sized, const CAP: usize> ExactSizeIterator for FixedChunkIterMut<'_, T, CAP> {}
+
+impl<T: Sized, const CAP: usize> ExactSizeIterator for FixedChunkIntoIter<T, CAP> {}
+
+impl<T: Sized, const CAP: usize> IntoIterator for FixedChunkDeque<T, CAP> {
+    type Item = T;
+    type IntoIter = FixedChunkIntoIter<T, CAP>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        FixedChunkIntoIter(self)
+    }
+}
+
+impl<'a, T: Sized, const CAP: usize> IntoIterator for &'a FixedChunkDeque<T, CAP> {
+    type Item = &'a T;
+    type IntoIter = FixedChunkIter<'a, T, CAP>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T: Sized, const CAP: usize> IntoIterator for &'a mut FixedChunkDeque<T, CAP> {
+    type Item = &'a mut T;
+    type IntoIter = FixedChunkIterMut<'a, T, CAP>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
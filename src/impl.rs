@@ -1,12 +1,45 @@
-use crate::{chunk::Chunk, *};
-use std::{alloc::Layout, collections::VecDeque};
+#[cfg(feature = "std")]
+use crate::release::IdleRelease;
+use crate::{
+    chunk::Chunk,
+    snapshot::{Cloner, SharedChunks},
+    *,
+};
+use alloc::{alloc::Layout, collections::VecDeque, vec::Vec};
+use core::{
+    cell::{Cell, RefCell},
+    mem::size_of,
+};
 
 pub struct PinnedDeque<T: Sized> {
-    size: usize,
-    cap_per_chunk: u32,
-    layout: Layout,
+    pub(crate) size: usize,
+    /// The sequence number of the front element.
+    pub(crate) head_seq: u64,
+    pub(crate) cap_per_chunk: u32,
+    /// `log2(cap_per_chunk)` if `cap_per_chunk` is a power of 2.
+    pub(crate) cap_shift: Option<u32>,
+    pub(crate) layout: Layout,
     pub(crate) used: VecDeque<*mut Chunk<T>>,
-    freed: Vec<*mut Chunk<T>>,
+    /// Addresses of chunks owned by the deque, sorted, with their sequence numbers
+    /// as of the last time they were attached.
+    /// A chunk is used if it is at its sequence number minus `front_seq` in `used`.
+    ///
+    /// Entries are added and removed only when chunks come into or leave the deque,
+    /// e.g., on allocation, so pushes and pops update a sequence number at most.
+    pub(crate) chunk_seqs: Vec<(usize, isize)>,
+    front_seq: isize,
+    pub(crate) freed: Vec<*mut Chunk<T>>,
+    /// Number of chunks at the bottom of `freed`, whose physical memory is released.
+    pub(crate) released: usize,
+    #[cfg(feature = "std")]
+    pub(crate) idle_release: Option<IdleRelease>,
+    /// `usize::MAX` if the deque is unbounded.
+    pub(crate) max_len: usize,
+    pub(crate) overflow_policy: OverflowPolicy,
+    /// Chunks shared with snapshots.
+    pub(crate) shared: RefCell<SharedChunks<T>>,
+    /// Clones elements in shared chunks. It is set on taking a snapshot.
+    pub(crate) cloner: Cell<Option<Cloner<T>>>,
 }
 
 impl<T> PinnedDeque<T>
@@ -15,44 +48,91 @@ where
 {
     /// Creates an empty deque with the adaptive capacity per chunk.
     ///
+    /// If rounding the capacity per chunk down to a power of 2 wastes little memory,
+    /// it is rounded, so that indexing is cheaper.
+    ///
     /// Caveat:
     /// The default capacity per chunk intends to fit a chunk into a memory page.
     /// So, if the size of a single element plus the chunk overhead (8B) exceeds
     /// the size of a memory page, do not use this constructor.
     pub fn new() -> Self {
+        Self::with_capacity_per_chunk(Self::adaptive_capacity_per_chunk())
+    }
+
+    pub(crate) fn adaptive_capacity_per_chunk() -> u32 {
         let cap_per_chunk = Chunk::<T>::capacity_per_chunk();
-        let layout = Chunk::<T>::layout(cap_per_chunk);
-        Self {
-            size: 0,
-            cap_per_chunk,
-            layout,
-            used: VecDeque::new(),
-            freed: Vec::new(),
+        let pow2 = 1 << cap_per_chunk.ilog2();
+        if cap_per_chunk - pow2 <= cap_per_chunk / 8 {
+            pow2
+        } else {
+            cap_per_chunk
         }
     }
 
     /// Creates an empty deque with the given capacity per chunk.
+    ///
+    /// Indexing is cheaper if `cap_per_chunk` is a power of 2.
     pub fn with_capacity_per_chunk(cap_per_chunk: u32) -> Self {
         let layout = Chunk::<T>::layout(cap_per_chunk);
+        let cap_shift = if cap_per_chunk.is_power_of_two() {
+            Some(cap_per_chunk.trailing_zeros())
+        } else {
+            None
+        };
         Self {
             size: 0,
+            head_seq: 0,
             cap_per_chunk,
+            cap_shift,
             layout,
             used: VecDeque::new(),
+            chunk_seqs: Vec::new(),
+            front_seq: 0,
             freed: Vec::new(),
+            released: 0,
+            #[cfg(feature = "std")]
+            idle_release: None,
+            max_len: usize::MAX,
+            overflow_policy: OverflowPolicy::Reject,
+            shared: RefCell::new(SharedChunks::new()),
+            cloner: Cell::new(None),
         }
     }
 
+    /// Creates an empty deque with the given capacity per chunk rounded up to a power of 2,
+    /// so that indexing is done by shifts and masks instead of divisions.
+    pub fn with_pow2_capacity_per_chunk(cap_per_chunk: u32) -> Self {
+        Self::with_capacity_per_chunk(cap_per_chunk.next_power_of_two())
+    }
+
     /// Reserves additional capacity in order to avoid memory allocations then.
     pub fn reserve(&mut self, additional: usize) {
+        if let Err(err) = self.try_reserve(additional) {
+            err.handle();
+        }
+    }
+
+    /// Tries to reserve additional capacity in order to avoid memory allocations then.
+    ///
+    /// Chunks allocated before the failure are kept.
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        if self.is_bounded() {
+            // All chunks are allocated on construction.
+            return Ok(());
+        }
         let cap_per_chunk = self.cap_per_chunk as usize;
         let n = additional.div_ceil(cap_per_chunk);
         if n > self.freed.len() {
+            self.freed.try_reserve(n - self.freed.len())?;
+            // Reserved chunks are moved into `used` on pushes.
+            self.try_reserve_used(n)?;
             for _ in self.freed.len()..n {
-                self.freed.push(Chunk::<T>::new(self.layout));
+                let chunk = self.try_new_chunk()?;
+                self.freed.push(chunk);
             }
         }
         debug_assert!(n <= self.freed.len());
+        Ok(())
     }
 
     pub fn len(&self) -> usize {
@@ -72,15 +152,47 @@ where
     }
 
     pub fn push_back(&mut self, elem: T) {
-        self.size += 1;
+        if self.is_full() {
+            // The element overflowing is dropped.
+            drop(self.overflow_back(elem));
+        } else {
+            self.push_back_unbounded(elem);
+        }
+    }
+
+    /// Pushes an element at the back, or gives it back if memory allocation fails
+    /// or a full bounded deque rejects it.
+    pub fn try_push_back(&mut self, elem: T) -> Result<(), TryPushError<T>> {
+        if self.is_full() {
+            if self.rejects_overflow() {
+                return Err(TryPushError::full(elem));
+            }
+            let _ = self.overflow_back(elem);
+            return Ok(());
+        }
+        self.try_push_back_unbounded(elem)
+    }
+
+    pub(crate) fn push_back_unbounded(&mut self, elem: T) {
+        if let Err(err) = self.try_push_back_unbounded(elem) {
+            err.handle();
+        }
+    }
+
+    fn try_push_back_unbounded(&mut self, elem: T) -> Result<(), TryPushError<T>> {
+        self.unshare_back_for_push();
         if let Some(back_chunk) = self.used.back() {
             let back_chunk = unsafe { &mut **back_chunk };
             if let Some(slot) = back_chunk.reserve_back(self.cap_per_chunk) {
                 slot.write(elem);
-                return;
+                self.size += 1;
+                return Ok(());
             }
         }
-        let new_chunk = self.fetch_a_freed_chunk();
+        let new_chunk = match self.try_fetch_a_freed_chunk() {
+            Ok(new_chunk) => new_chunk,
+            Err(err) => return Err(TryPushError::new(elem, err)),
+        };
         unsafe {
             let new_chunk = &mut *new_chunk;
             new_chunk.reset_for_back_insertion();
@@ -89,34 +201,102 @@ where
                 .unwrap_unchecked()
                 .write(elem);
         }
-        self.used.push_back(new_chunk);
+        self.attach_back(new_chunk);
+        self.size += 1;
+        Ok(())
     }
 
     pub fn push_front(&mut self, elem: T) {
-        self.size += 1;
+        if self.is_full() {
+            // The element overflowing is dropped.
+            drop(self.overflow_front(elem));
+        } else {
+            self.push_front_unbounded(elem);
+        }
+    }
+
+    /// Pushes an element at the front, or gives it back if memory allocation fails
+    /// or a full bounded deque rejects it.
+    pub fn try_push_front(&mut self, elem: T) -> Result<(), TryPushError<T>> {
+        if self.is_full() {
+            if self.rejects_overflow() {
+                return Err(TryPushError::full(elem));
+            }
+            let _ = self.overflow_front(elem);
+            return Ok(());
+        }
+        self.try_push_front_unbounded(elem)
+    }
+
+    pub(crate) fn push_front_unbounded(&mut self, elem: T) {
+        if let Err(err) = self.try_push_front_unbounded(elem) {
+            err.handle();
+        }
+    }
+
+    fn try_push_front_unbounded(&mut self, elem: T) -> Result<(), TryPushError<T>> {
+        self.unshare_front_for_push();
         if let Some(front_chunk) = self.used.front() {
             let front_chunk = unsafe { &mut **front_chunk };
             if let Some(slot) = front_chunk.reserve_front() {
                 slot.write(elem);
-                return;
+                self.size += 1;
+                self.head_seq = self.head_seq.wrapping_sub(1);
+                return Ok(());
             }
         }
-        let new_chunk = self.fetch_a_freed_chunk();
+        let new_chunk = match self.try_fetch_a_freed_chunk() {
+            Ok(new_chunk) => new_chunk,
+            Err(err) => return Err(TryPushError::new(elem, err)),
+        };
         unsafe {
             let new_chunk = &mut *new_chunk;
             new_chunk.reset_for_front_insertion(self.cap_per_chunk);
             new_chunk.reserve_front().unwrap_unchecked().write(elem);
         }
-        self.used.push_front(new_chunk);
+        self.attach_front(new_chunk);
+        self.size += 1;
+        self.head_seq = self.head_seq.wrapping_sub(1);
+        Ok(())
+    }
+
+    /// Pushes all elements of `iter` at the back.
+    ///
+    /// On failure of memory allocation, the element failed to be pushed is given back,
+    /// and those remaining in `iter` are dropped.
+    pub fn try_extend<I: IntoIterator<Item = T>>(
+        &mut self,
+        iter: I,
+    ) -> Result<(), TryPushError<T>> {
+        let iter = iter.into_iter();
+        let (size_hint, _) = iter.size_hint();
+        // A failure here is not fatal. It will be reported by the very push failing.
+        let _ = self.try_reserve(size_hint);
+        for x in iter {
+            self.try_push_back(x)?;
+        }
+        Ok(())
     }
 
     pub fn pop_back(&mut self) -> Option<T> {
         if let Some(back_chunk) = self.used.back() {
-            let back_chunk = unsafe { &mut **back_chunk };
-            let res = back_chunk.pop_back();
+            let back_chunk_ptr = *back_chunk;
+            let back_chunk = unsafe { &mut *back_chunk_ptr };
+            let shared = self.is_shared(back_chunk_ptr);
+            let res = if shared {
+                let res = self.clone_shared(back_chunk.back());
+                back_chunk.discard_back();
+                res
+            } else {
+                back_chunk.pop_back()
+            };
             if back_chunk.len() == 0 {
-                let last_chunk = unsafe { self.used.pop_back().unwrap_unchecked() };
-                self.recycle(last_chunk);
+                let last_chunk = unsafe { self.detach_back().unwrap_unchecked() };
+                if shared {
+                    self.give_up_shared(last_chunk);
+                } else {
+                    self.recycle_unshared(last_chunk);
+                }
             }
             self.size -= 1;
             Some(res)
@@ -127,13 +307,26 @@ where
 
     pub fn pop_front(&mut self) -> Option<T> {
         if let Some(front_chunk) = self.used.front() {
-            let front_chunk = unsafe { &mut **front_chunk };
-            let res = front_chunk.pop_front();
+            let front_chunk_ptr = *front_chunk;
+            let front_chunk = unsafe { &mut *front_chunk_ptr };
+            let shared = self.is_shared(front_chunk_ptr);
+            let res = if shared {
+                let res = self.clone_shared(front_chunk.front());
+                front_chunk.discard_front();
+                res
+            } else {
+                front_chunk.pop_front()
+            };
             if front_chunk.len() == 0 {
-                let first_chunk = unsafe { self.used.pop_front().unwrap_unchecked() };
-                self.recycle(first_chunk);
+                let first_chunk = unsafe { self.detach_front().unwrap_unchecked() };
+                if shared {
+                    self.give_up_shared(first_chunk);
+                } else {
+                    self.recycle_unshared(first_chunk);
+                }
             }
             self.size -= 1;
+            self.head_seq = self.head_seq.wrapping_add(1);
             Some(res)
         } else {
             None
@@ -155,6 +348,9 @@ where
     }
 
     pub fn back_mut(&mut self) -> Option<&mut T> {
+        if !self.used.is_empty() {
+            self.unshare(self.used.len() - 1);
+        }
         self.used.back().map(|back_chunk| {
             let back_chunk = unsafe { &mut **back_chunk };
             back_chunk.back_mut()
@@ -162,6 +358,9 @@ where
     }
 
     pub fn front_mut(&mut self) -> Option<&mut T> {
+        if !self.used.is_empty() {
+            self.unshare(0);
+        }
         self.used.front().map(|front_chunk| {
             let front_chunk = unsafe { &mut **front_chunk };
             front_chunk.front_mut()
@@ -169,63 +368,208 @@ where
     }
 
     pub fn clear(&mut self) {
-        while let Some(chunk_ptr) = self.used.pop_front() {
-            let chunk = unsafe { &mut *chunk_ptr };
-            chunk.drop_all();
-            self.recycle(chunk_ptr);
+        while let Some(chunk_ptr) = self.detach_front() {
+            if self.is_shared(chunk_ptr) {
+                self.give_up_shared(chunk_ptr);
+            } else {
+                let chunk = unsafe { &mut *chunk_ptr };
+                chunk.drop_all();
+                self.recycle_unshared(chunk_ptr);
+            }
         }
+        self.head_seq = self.head_seq.wrapping_add(self.size as u64);
+        self.size = 0;
     }
 
-    pub fn get(&self, mut idx: usize) -> Option<&T> {
+    pub fn get(&self, idx: usize) -> Option<&T> {
+        let (chunk, slot) = self.locate(idx)?;
+        let chunk = unsafe { &*(chunk as *const Chunk<T>) };
+        Some(chunk.slot(slot))
+    }
+
+    pub fn get_mut(&mut self, idx: usize) -> Option<&mut T> {
+        let (n, slot) = self.locate_in_used(idx)?;
+        self.unshare(n);
+        let chunk = unsafe { &mut **self.used.get(n).unwrap_unchecked() };
+        Some(chunk.slot_mut(slot))
+    }
+
+    /// Returns the index of an element in this deque, or `None` if `elem` is not in it.
+    ///
+    /// It takes O(log(number of chunks)) time.
+    /// It always returns `None` for zero-sized `T`, whose elements have no distinct addresses.
+    pub fn index_of(&self, elem: &T) -> Option<usize> {
+        self.index_of_ptr(elem)
+    }
+
+    /// Returns whether `ptr` points to an element in this deque.
+    ///
+    /// It takes O(log(number of chunks)) time.
+    pub fn contains_ptr(&self, ptr: *const T) -> bool {
+        self.index_of_ptr(ptr).is_some()
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter::new(self)
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        self.unshare_all();
+        IterMut::new(self)
+    }
+
+    /// Finds the chunk holding the `idx`-th element and the slot in that chunk.
+    ///
+    /// All chunks but the first one start at 0, and all chunks but the last one are full.
+    /// So, an element can be located as if the first chunk were full as well.
+    fn locate(&self, idx: usize) -> Option<(*mut Chunk<T>, usize)> {
+        let (n, slot) = self.locate_in_used(idx)?;
+        let chunk = unsafe { *self.used.get(n).unwrap_unchecked() };
+        Some((chunk, slot))
+    }
+
+    /// Like `locate()`, but returns the position of the chunk in `used`.
+    pub(crate) fn locate_in_used(&self, idx: usize) -> Option<(usize, usize)> {
         if idx >= self.len() {
             return None;
         }
         let first_chunk = unsafe { &*(*self.used.front().unwrap_unchecked() as *const Chunk<T>) };
-        if idx < first_chunk.len() {
-            return Some(first_chunk.get(idx));
-        } else {
-            idx -= first_chunk.len();
-        }
-        let n = idx / (self.cap_per_chunk as usize);
-        let offset = idx % (self.cap_per_chunk as usize);
-        let target_chunk = unsafe { &*(self.used[n + 1] as *const Chunk<T>) };
-        Some(target_chunk.get(offset))
+        let idx = idx + first_chunk.start as usize;
+        let (n, slot) = match self.cap_shift {
+            Some(shift) => (idx >> shift, idx & ((1 << shift) - 1)),
+            None => {
+                let cap_per_chunk = self.cap_per_chunk as usize;
+                (idx / cap_per_chunk, idx % cap_per_chunk)
+            }
+        };
+        Some((n, slot))
     }
 
-    pub fn get_mut(&mut self, mut idx: usize) -> Option<&mut T> {
-        if idx >= self.len() {
+    fn index_of_ptr(&self, ptr: *const T) -> Option<usize> {
+        if size_of::<T>() == 0 {
+            // All elements share the same address.
             return None;
         }
-        let front_chunk = unsafe { &mut **self.used.front().unwrap_unchecked() };
-        if idx < front_chunk.len() {
-            return Some(front_chunk.get_mut(idx));
-        } else {
-            idx -= front_chunk.len();
+        let addr = ptr as usize;
+        let i = self
+            .chunk_seqs
+            .partition_point(|&(chunk_addr, _)| chunk_addr <= addr);
+        let (chunk_addr, seq) = self.chunk_seqs[i.checked_sub(1)?];
+        // The chunk may be a spare one, whose sequence number is stale.
+        let n = usize::try_from(seq - self.front_seq).ok()?;
+        if self.used.get(n).map(|chunk| *chunk as usize) != Some(chunk_addr) {
+            return None;
         }
-        let n = idx / (self.cap_per_chunk as usize);
-        let offset = idx % (self.cap_per_chunk as usize);
-        let target_chunk = unsafe { &mut *self.used[n + 1] };
-        Some(target_chunk.get_mut(offset))
+        let offset = addr.checked_sub(chunk_addr + size_of::<Chunk<T>>())?;
+        if offset % size_of::<T>() != 0 {
+            return None;
+        }
+        let slot = offset / size_of::<T>();
+        let chunk = unsafe { &*(chunk_addr as *const Chunk<T>) };
+        if slot < chunk.start as usize || slot >= chunk.end as usize {
+            return None;
+        }
+        let first_chunk = unsafe { &*(*self.used.front().unwrap_unchecked() as *const Chunk<T>) };
+        Some(n * self.cap_per_chunk as usize + slot - first_chunk.start as usize)
     }
 
-    pub fn iter(&self) -> Iter<'_, T> {
-        Iter::new(self)
+    pub(crate) fn attach_back(&mut self, chunk: *mut Chunk<T>) {
+        let seq = self.front_seq + self.used.len() as isize;
+        self.set_chunk_seq(chunk, seq);
+        self.used.push_back(chunk);
     }
 
-    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
-        IterMut::new(self)
+    pub(crate) fn attach_front(&mut self, chunk: *mut Chunk<T>) {
+        self.front_seq -= 1;
+        self.set_chunk_seq(chunk, self.front_seq);
+        self.used.push_front(chunk);
+    }
+
+    /// A detached chunk stays in `chunk_seqs` until it leaves the deque.
+    pub(crate) fn detach_back(&mut self) -> Option<*mut Chunk<T>> {
+        self.used.pop_back()
+    }
+
+    pub(crate) fn detach_front(&mut self) -> Option<*mut Chunk<T>> {
+        let chunk = self.used.pop_front()?;
+        self.front_seq += 1;
+        Some(chunk)
+    }
+
+    /// Puts `chunk` in place of the `n`-th used chunk.
+    pub(crate) fn replace_used(&mut self, n: usize, chunk: *mut Chunk<T>) {
+        self.used[n] = chunk;
+        self.set_chunk_seq(chunk, self.front_seq + n as isize);
+    }
+
+    /// Updates the sequence number of a chunk, or adds it if the chunk is new to the deque.
+    fn set_chunk_seq(&mut self, chunk: *mut Chunk<T>, seq: isize) {
+        let addr = chunk as usize;
+        let i = self
+            .chunk_seqs
+            .partition_point(|&(chunk_addr, _)| chunk_addr < addr);
+        match self.chunk_seqs.get_mut(i) {
+            Some(entry) if entry.0 == addr => entry.1 = seq,
+            _ => self.chunk_seqs.insert(i, (addr, seq)),
+        }
+    }
+
+    /// Removes a chunk leaving the deque, e.g., to snapshots or another deque.
+    pub(crate) fn forget_chunk(&mut self, chunk: *mut Chunk<T>) {
+        let addr = chunk as usize;
+        let i = self
+            .chunk_seqs
+            .partition_point(|&(chunk_addr, _)| chunk_addr < addr);
+        if self.chunk_seqs.get(i).is_some_and(|entry| entry.0 == addr) {
+            self.chunk_seqs.remove(i);
+        }
+    }
+
+    /// Makes sure that `additional` chunks can be attached without allocations.
+    pub(crate) fn try_reserve_used(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.used.try_reserve(additional)?;
+        Ok(())
+    }
+
+    /// Allocates a chunk owned by the deque.
+    pub(crate) fn try_new_chunk(&mut self) -> Result<*mut Chunk<T>, TryReserveError> {
+        self.chunk_seqs.try_reserve(1)?;
+        let chunk = Chunk::<T>::try_new(self.layout)?;
+        self.set_chunk_seq(chunk, self.front_seq);
+        Ok(chunk)
     }
 
-    fn fetch_a_freed_chunk(&mut self) -> *mut Chunk<T> {
+    /// Fetches a chunk, and makes sure that it can be attached without allocations.
+    fn try_fetch_a_freed_chunk(&mut self) -> Result<*mut Chunk<T>, TryReserveError> {
+        self.try_reserve_used(1)?;
         if let Some(chunk) = self.freed.pop() {
-            chunk
+            self.released = self.released.min(self.freed.len());
+            #[cfg(feature = "std")]
+            self.touch_freed();
+            Ok(chunk)
         } else {
-            Chunk::<T>::new(self.layout)
+            self.try_new_chunk()
+        }
+    }
+
+    pub(crate) fn fetch_a_freed_chunk(&mut self) -> *mut Chunk<T> {
+        match self.try_fetch_a_freed_chunk() {
+            Ok(chunk) => chunk,
+            Err(err) => err.handle(),
+        }
+    }
+
+    pub(crate) fn recycle(&mut self, chunk: *mut Chunk<T>) {
+        if !self.release_shared(chunk) {
+            self.recycle_unshared(chunk);
         }
     }
 
-    fn recycle(&mut self, chunk: *mut Chunk<T>) {
+    /// Keeps a chunk for later use, which is known not to be shared.
+    pub(crate) fn recycle_unshared(&mut self, chunk: *mut Chunk<T>) {
         self.freed.push(chunk);
+        #[cfg(feature = "std")]
+        self.release_memory_if_idle();
     }
 }
 
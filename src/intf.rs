@@ -1,4 +1,12 @@
-use crate::*;
+use crate::{chunk::Chunk, *};
+use alloc::{boxed::Box, collections::VecDeque, vec::Vec};
+use core::{
+    fmt,
+    hash::{Hash, Hasher},
+    mem::ManuallyDrop,
+    ops::{Index, IndexMut},
+    ptr,
+};
 
 impl<T> Default for PinnedDeque<T>
 where
@@ -9,11 +17,11 @@ where
     }
 }
 
-impl<T> std::fmt::Debug for PinnedDeque<T>
+impl<T> fmt::Debug for PinnedDeque<T>
 where
-    T: Sized + std::fmt::Debug,
+    T: Sized + fmt::Debug,
 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "[")?;
         let mut it = self.iter();
         if let Some(first) = it.next() {
@@ -27,7 +35,7 @@ where
     }
 }
 
-impl<T> std::iter::Extend<T> for PinnedDeque<T>
+impl<T> Extend<T> for PinnedDeque<T>
 where
     T: Sized,
 {
@@ -63,8 +71,129 @@ impl<T> From<Vec<T>> for PinnedDeque<T>
 where
     T: Sized,
 {
+    /// Copies elements chunk by chunk.
     fn from(value: Vec<T>) -> Self {
-        value.into_iter().collect()
+        let mut value = ManuallyDrop::new(value);
+        let mut res = Self::new();
+        unsafe {
+            res.append_raw(value.as_ptr(), value.len());
+            value.set_len(0);
+            ManuallyDrop::drop(&mut value);
+        }
+        res
+    }
+}
+
+impl<T> From<VecDeque<T>> for PinnedDeque<T>
+where
+    T: Sized,
+{
+    /// Copies elements chunk by chunk.
+    ///
+    /// The `VecDeque` is made contiguous in place first.
+    fn from(value: VecDeque<T>) -> Self {
+        Vec::from(value).into()
+    }
+}
+
+impl<T> From<PinnedDeque<T>> for Vec<T>
+where
+    T: Sized,
+{
+    /// Copies elements chunk by chunk.
+    ///
+    /// Elements in chunks shared with snapshots are cloned instead.
+    fn from(value: PinnedDeque<T>) -> Self {
+        let mut value = value;
+        value.unshare_all();
+        let mut res = Vec::<T>::with_capacity(value.len());
+        for chunk in value.used.iter() {
+            let chunk = unsafe { &mut **chunk };
+            let len = chunk.len();
+            unsafe {
+                let dst = res.as_mut_ptr().add(res.len());
+                ptr::copy_nonoverlapping(chunk.as_slice().as_ptr(), dst, len);
+                res.set_len(res.len() + len);
+            }
+            // Elements are moved out, so that dropping the deque does not drop them.
+            chunk.end = chunk.start;
+        }
+        value.size = 0;
+        res
+    }
+}
+
+impl<T> From<PinnedDeque<T>> for VecDeque<T>
+where
+    T: Sized,
+{
+    /// Copies elements chunk by chunk.
+    fn from(value: PinnedDeque<T>) -> Self {
+        Vec::from(value).into()
+    }
+}
+
+impl<T> PinnedDeque<T>
+where
+    T: Sized,
+{
+    pub fn into_boxed_slice(self) -> Box<[T]> {
+        Vec::from(self).into_boxed_slice()
+    }
+
+    pub fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        let mut res = Vec::with_capacity(self.len());
+        for chunk in self.used.iter() {
+            res.extend_from_slice(unsafe { &**chunk }.as_slice());
+        }
+        res
+    }
+
+    /// Moves `len` elements at `src` to the back, filling up chunks by `copy_nonoverlapping`.
+    ///
+    /// # Safety
+    ///
+    /// `src` must be valid for reading `len` elements, which must not be used afterward.
+    unsafe fn append_raw(&mut self, src: *const T, len: usize) {
+        let cap_per_chunk = self.cap_per_chunk;
+        let mut src = src;
+        let mut len = len;
+        self.unshare_back_for_push();
+        while len > 0 {
+            let chunk = match self.used.back() {
+                Some(back_chunk) if (**back_chunk).end < cap_per_chunk => *back_chunk,
+                _ => {
+                    let chunk = self.fetch_a_freed_chunk();
+                    (*chunk).reset_for_back_insertion();
+                    self.attach_back(chunk);
+                    chunk
+                }
+            };
+            let chunk = &mut *chunk;
+            let n = ((cap_per_chunk - chunk.end) as usize).min(len);
+            let dst = (Chunk::elems(chunk) as *mut T).add(chunk.end as usize);
+            ptr::copy_nonoverlapping(src, dst, n);
+            chunk.end += n as u32;
+            self.size += n;
+            src = src.add(n);
+            len -= n;
+        }
+    }
+}
+
+impl<T> Hash for PinnedDeque<T>
+where
+    T: Sized + Hash,
+{
+    /// Hashes the same as `VecDeque`, i.e., the length followed by elements.
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len());
+        for x in self.iter() {
+            x.hash(state);
+        }
     }
 }
 
@@ -97,3 +226,31 @@ where
         self.extend(source.iter().cloned());
     }
 }
+
+impl<T> Index<usize> for PinnedDeque<T>
+where
+    T: Sized,
+{
+    type Output = T;
+
+    fn index(&self, idx: usize) -> &T {
+        let len = self.len();
+        match self.get(idx) {
+            Some(elem) => elem,
+            None => panic!("index out of bounds: the len is {len} but the index is {idx}"),
+        }
+    }
+}
+
+impl<T> IndexMut<usize> for PinnedDeque<T>
+where
+    T: Sized,
+{
+    fn index_mut(&mut self, idx: usize) -> &mut T {
+        let len = self.len();
+        match self.get_mut(idx) {
+            Some(elem) => elem,
+            None => panic!("index out of bounds: the len is {len} but the index is {idx}"),
+        }
+    }
+}
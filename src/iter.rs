@@ -1,5 +1,6 @@
 use crate::{chunk::Chunk, *};
-use std::{collections::*, iter::*, ptr};
+use alloc::collections::*;
+use core::{iter::*, ptr};
 
 #[derive(Clone)]
 pub struct Iter<'a, T: Sized> {
@@ -216,6 +217,48 @@ where
     }
 }
 
+impl<'a, T> Iter<'a, T>
+where
+    T: Sized,
+{
+    /// Creates an iterator starting at the `idx`-th element.
+    pub(crate) fn new_from(deque: &'a PinnedDeque<T>, idx: usize) -> Self {
+        let Some((n, slot)) = deque.locate_in_used(idx) else {
+            return Self {
+                size: 0,
+                chunk_iter: deque.used.range(0..0),
+                front_chunk: ptr::null(),
+                front_elem: ptr::null(),
+                back_chunk: ptr::null(),
+                back_elem: ptr::null(),
+            };
+        };
+        let mut chunk_iter = deque.used.range(n..);
+        let front_chunk = unsafe { *chunk_iter.next().unwrap_unchecked() as *const Chunk<T> };
+        let front_elem: *const _ = unsafe {
+            let front_chunk: &_ = &*front_chunk;
+            front_chunk.slot(slot)
+        };
+        let back_chunk: *const _ = if let Some(back_chunk) = chunk_iter.next_back() {
+            *back_chunk as *const Chunk<T>
+        } else {
+            front_chunk
+        };
+        let back_elem: *const _ = unsafe {
+            let back_chunk: &Chunk<T> = &*back_chunk;
+            back_chunk.back()
+        };
+        Self {
+            size: deque.len() - idx,
+            chunk_iter,
+            front_chunk,
+            front_elem,
+            back_chunk,
+            back_elem,
+        }
+    }
+}
+
 impl<'a, T> IterMut<'a, T>
 where
     T: Sized,
@@ -283,6 +326,6 @@ impl<'a, T: Sized> IntoIterator for &'a mut PinnedDeque<T> {
     type IntoIter = IterMut<'a, T>;
 
     fn into_iter(self) -> Self::IntoIter {
-        IterMut::new(self)
+        self.iter_mut()
     }
 }
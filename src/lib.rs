@@ -1,4 +1,7 @@
 #![doc = include_str!("../README.md")]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
 
 mod r#impl;
 pub use self::r#impl::*;
@@ -7,7 +10,45 @@ mod cmp;
 mod intf;
 mod iter;
 pub use self::iter::*;
+mod bounded;
+#[cfg(feature = "bytes")]
+mod buf;
+#[cfg(feature = "bytes")]
+pub use self::buf::*;
+mod byte_deque;
+pub use self::byte_deque::*;
+#[cfg(feature = "std")]
+pub mod lru;
+pub use self::bounded::*;
+#[cfg(feature = "std")]
+pub mod channel;
 mod chunk;
+mod dyn_deque;
+pub use self::dyn_deque::*;
+mod emplace;
+mod error;
+pub use self::error::*;
+mod fixed;
+pub use self::fixed::*;
+mod owned_chunk;
+pub use self::owned_chunk::*;
+mod peek;
+pub use self::peek::*;
+#[cfg(feature = "pin-init")]
+mod pin_init;
+#[cfg(feature = "pin-init")]
+pub use pinned_init;
+mod release;
+mod seq;
+mod snapshot;
+pub use self::snapshot::*;
+mod splice;
+mod stats;
+pub use self::stats::*;
+mod view;
+pub use self::view::*;
+mod windows;
+pub use self::windows::*;
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,298 @@
+//! A least-recently-used cache built on [`PinnedDeque`].
+//!
+//! Entries are kept in slots of a pinned deque.
+//! So, a value never moves until it is removed or evicted,
+//! no matter how many other entries are inserted or touched.
+
+use crate::PinnedDeque;
+use std::{
+    borrow::Borrow,
+    collections::{HashMap, VecDeque},
+    hash::{Hash, Hasher},
+    mem,
+};
+
+pub struct LruCache<K, V> {
+    cap: usize,
+    slots: PinnedDeque<Option<Entry<K, V>>>,
+    vacant: Vec<usize>,
+    /// Keys point into `slots`, as entries never move.
+    index: HashMap<KeyRef<K>, usize>,
+    /// Slots from the least recently used to the most.
+    /// A record is a tombstone if its stamp differs from the one in the slot.
+    recency: VecDeque<(usize, u64)>,
+    next_stamp: u64,
+}
+
+// SAFETY: `KeyRef`s only point into `slots`, which is owned by the cache.
+// The deque in `slots` never shares chunks with snapshots,
+// and shared references to the cache only read it through `PinnedDeque::get()`.
+unsafe impl<K: Send, V: Send> Send for LruCache<K, V> {}
+unsafe impl<K: Sync, V: Sync> Sync for LruCache<K, V> {}
+
+/// The result of [`LruCache::push()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Insertion<K, V> {
+    /// The key was new, and no entry was evicted.
+    Inserted,
+    /// The key was present, and its old value was replaced.
+    Replaced(V),
+    /// The key was new, and the least recently used entry was evicted to make room.
+    Evicted(K, V),
+}
+
+struct Entry<K, V> {
+    key: K,
+    value: V,
+    stamp: u64,
+}
+
+impl<K, V> LruCache<K, V>
+where
+    K: Hash + Eq,
+{
+    /// Creates an empty cache holding at most `cap` entries.
+    pub fn new(cap: usize) -> Self {
+        assert!(cap > 0, "capacity of a LRU cache must be positive");
+        Self {
+            cap,
+            slots: PinnedDeque::new(),
+            vacant: Vec::new(),
+            index: HashMap::new(),
+            recency: VecDeque::new(),
+            next_stamp: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.cap
+    }
+
+    /// Inserts an entry and marks it as the most recently used.
+    ///
+    /// If the key is present, its value is replaced in place and the old one is returned.
+    /// Otherwise, if the cache is full, the least recently used entry is evicted and dropped.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.push(key, value) {
+            Insertion::Replaced(old) => Some(old),
+            Insertion::Inserted | Insertion::Evicted(..) => None,
+        }
+    }
+
+    /// Like `insert()`, but also gives back the entry evicted, if any.
+    pub fn push(&mut self, key: K, value: V) -> Insertion<K, V> {
+        if let Some(&slot) = self.index.get(KeyWrapper::from_ref(&key)) {
+            let entry = self.entry_mut(slot);
+            let old = mem::replace(&mut entry.value, value);
+            self.touch(slot);
+            return Insertion::Replaced(old);
+        }
+        let evicted = if self.len() >= self.cap {
+            self.pop_lru()
+        } else {
+            None
+        };
+        let slot = if let Some(slot) = self.vacant.pop() {
+            slot
+        } else {
+            self.slots.push_back(None);
+            self.slots.len() - 1
+        };
+        let entry = unsafe { self.slots.get_mut(slot).unwrap_unchecked() };
+        let entry = entry.insert(Entry {
+            key,
+            value,
+            stamp: 0,
+        });
+        self.index.insert(KeyRef(&entry.key), slot);
+        self.touch(slot);
+        match evicted {
+            Some((key, value)) => Insertion::Evicted(key, value),
+            None => Insertion::Inserted,
+        }
+    }
+
+    /// Returns the value of the key, and marks the entry as the most recently used.
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let slot = *self.index.get(KeyWrapper::from_ref(key))?;
+        self.touch(slot);
+        Some(&self.entry_mut(slot).value)
+    }
+
+    /// Returns the value of the key, and marks the entry as the most recently used.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let slot = *self.index.get(KeyWrapper::from_ref(key))?;
+        self.touch(slot);
+        Some(&mut self.entry_mut(slot).value)
+    }
+
+    /// Returns the value of the key without touching the entry.
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let slot = *self.index.get(KeyWrapper::from_ref(key))?;
+        Some(&self.entry(slot).value)
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.index.contains_key(KeyWrapper::from_ref(key))
+    }
+
+    /// Removes the entry of the key, and returns its value.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let slot = *self.index.get(KeyWrapper::from_ref(key))?;
+        let (_, value) = self.take(slot);
+        Some(value)
+    }
+
+    /// Removes the least recently used entry, and returns it.
+    pub fn pop_lru(&mut self) -> Option<(K, V)> {
+        while let Some((slot, stamp)) = self.recency.pop_front() {
+            if self.is_live(slot, stamp) {
+                return Some(self.take(slot));
+            }
+        }
+        None
+    }
+
+    /// Returns the least recently used entry without touching it.
+    pub fn peek_lru(&self) -> Option<(&K, &V)> {
+        self.iter().next_back()
+    }
+
+    /// Iterates entries from the most recently used to the least.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&K, &V)> + '_ {
+        self.recency
+            .iter()
+            .rev()
+            .filter(|(slot, stamp)| self.is_live(*slot, *stamp))
+            .map(|(slot, _)| {
+                let entry = self.entry(*slot);
+                (&entry.key, &entry.value)
+            })
+    }
+
+    pub fn clear(&mut self) {
+        self.index.clear();
+        self.recency.clear();
+        self.vacant.clear();
+        self.slots.clear();
+    }
+
+    fn entry(&self, slot: usize) -> &Entry<K, V> {
+        let entry = unsafe { self.slots.get(slot).unwrap_unchecked() };
+        unsafe { entry.as_ref().unwrap_unchecked() }
+    }
+
+    fn entry_mut(&mut self, slot: usize) -> &mut Entry<K, V> {
+        let entry = unsafe { self.slots.get_mut(slot).unwrap_unchecked() };
+        unsafe { entry.as_mut().unwrap_unchecked() }
+    }
+
+    fn is_live(&self, slot: usize, stamp: u64) -> bool {
+        let entry = unsafe { self.slots.get(slot).unwrap_unchecked() };
+        matches!(entry, Some(entry) if entry.stamp == stamp)
+    }
+
+    /// Marks the entry in the slot as the most recently used.
+    /// The former record of the slot in `recency`, if any, becomes a tombstone.
+    fn touch(&mut self, slot: usize) {
+        let stamp = self.next_stamp;
+        self.next_stamp += 1;
+        self.entry_mut(slot).stamp = stamp;
+        self.recency.push_back((slot, stamp));
+        if self.recency.len() > 2 * self.len() + 16 {
+            let slots = &self.slots;
+            self.recency.retain(|(slot, stamp)| {
+                let entry = unsafe { slots.get(*slot).unwrap_unchecked() };
+                matches!(entry, Some(entry) if entry.stamp == *stamp)
+            });
+        }
+    }
+
+    fn take(&mut self, slot: usize) -> (K, V) {
+        let entry = unsafe { self.slots.get_mut(slot).unwrap_unchecked() };
+        // The key in the index points to the entry, so remove it before the entry moves out.
+        let key = unsafe { &entry.as_ref().unwrap_unchecked().key };
+        self.index.remove(KeyWrapper::from_ref(key));
+        let entry = unsafe { entry.take().unwrap_unchecked() };
+        self.vacant.push(slot);
+        (entry.key, entry.value)
+    }
+}
+
+struct KeyRef<K>(*const K);
+
+impl<K: Hash> Hash for KeyRef<K> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        unsafe { &*self.0 }.hash(state)
+    }
+}
+
+impl<K: PartialEq> PartialEq for KeyRef<K> {
+    fn eq(&self, other: &Self) -> bool {
+        unsafe { *self.0 == *other.0 }
+    }
+}
+
+impl<K: Eq> Eq for KeyRef<K> {}
+
+/// Allows looking up `KeyRef<K>` by `&Q`, where `K: Borrow<Q>`.
+#[repr(transparent)]
+struct KeyWrapper<Q: ?Sized>(Q);
+
+impl<Q: ?Sized> KeyWrapper<Q> {
+    fn from_ref(key: &Q) -> &Self {
+        unsafe { &*(key as *const Q as *const Self) }
+    }
+}
+
+impl<Q: Hash + ?Sized> Hash for KeyWrapper<Q> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl<Q: PartialEq + ?Sized> PartialEq for KeyWrapper<Q> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<Q: Eq + ?Sized> Eq for KeyWrapper<Q> {}
+
+impl<K, Q> Borrow<KeyWrapper<Q>> for KeyRef<K>
+where
+    K: Borrow<Q>,
+    Q: ?Sized,
+{
+    fn borrow(&self) -> &KeyWrapper<Q> {
+        KeyWrapper::from_ref(unsafe { &*self.0 }.borrow())
+    }
+}
@@ -0,0 +1,215 @@
+use crate::{chunk::Chunk, *};
+use alloc::{alloc::Layout, vec::Vec};
+use core::{
+    fmt,
+    iter::FusedIterator,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    ptr, slice,
+};
+
+/// A chunk of elements taken out of a deque, which owns its allocation.
+///
+/// It can be sent to another thread and pushed into another deque without copying elements.
+pub struct OwnedChunk<T: Sized> {
+    ptr: *mut Chunk<T>,
+    layout: Layout,
+    cap_per_chunk: u32,
+    _marker: PhantomData<T>,
+}
+
+unsafe impl<T: Send> Send for OwnedChunk<T> {}
+unsafe impl<T: Sync> Sync for OwnedChunk<T> {}
+
+impl<T: Sized> OwnedChunk<T> {
+    /// Returns how many elements the chunk can hold.
+    pub fn capacity(&self) -> usize {
+        self.cap_per_chunk as usize
+    }
+
+    /// Moves elements into a `Vec`, and frees the chunk.
+    pub fn into_vec(mut self) -> Vec<T> {
+        let chunk = self.chunk_mut();
+        let len = chunk.len();
+        let mut res = Vec::with_capacity(len);
+        unsafe {
+            let src = Chunk::elems(chunk).add(chunk.start as usize);
+            ptr::copy_nonoverlapping(src, res.as_mut_ptr(), len);
+            res.set_len(len);
+        }
+        chunk.start = chunk.end;
+        res
+    }
+
+    fn chunk(&self) -> &Chunk<T> {
+        unsafe { &*self.ptr }
+    }
+
+    fn chunk_mut(&mut self) -> &mut Chunk<T> {
+        unsafe { &mut *self.ptr }
+    }
+
+    /// Gives up the chunk without dropping elements or freeing it.
+    fn into_raw(self) -> *mut Chunk<T> {
+        let ptr = self.ptr;
+        core::mem::forget(self);
+        ptr
+    }
+}
+
+impl<T> PinnedDeque<T>
+where
+    T: Sized,
+{
+    /// Takes the front chunk out of the deque with its elements.
+    ///
+    /// Elements stay where they are. If the chunk is shared with snapshots, it is copied first.
+    pub fn pop_front_chunk(&mut self) -> Option<OwnedChunk<T>> {
+        if self.used.is_empty() {
+            return None;
+        }
+        self.unshare(0);
+        let chunk = unsafe { self.detach_front().unwrap_unchecked() };
+        self.forget_chunk(chunk);
+        let len = unsafe { &*chunk }.len();
+        self.size -= len;
+        self.head_seq = self.head_seq.wrapping_add(len as u64);
+        Some(OwnedChunk {
+            ptr: chunk,
+            layout: self.layout,
+            cap_per_chunk: self.cap_per_chunk,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Pushes the elements of a chunk at the back.
+    ///
+    /// The chunk itself is linked into the deque, and elements in it stay pinned,
+    /// if it has the same capacity and lines up with the back chunk,
+    /// i.e., the back chunk is full and `chunk` has no free slots at its front.
+    /// Otherwise, e.g., if the deque is bounded, elements are moved one by one.
+    ///
+    /// A bounded deque follows its overflow policy for each element, as `push_back()` does,
+    /// except that under [`OverflowPolicy::Reject`] a chunk which does not fit is given back
+    /// as a whole, and nothing is pushed.
+    pub fn push_back_chunk(&mut self, chunk: OwnedChunk<T>) -> Option<OwnedChunk<T>> {
+        if self.is_bounded() && chunk.len() > self.remaining() && self.rejects_overflow() {
+            return Some(chunk);
+        }
+        let mut chunk = chunk;
+        let same_layout = chunk.cap_per_chunk == self.cap_per_chunk && chunk.layout == self.layout;
+        let lines_up = match self.used.back() {
+            Some(back_chunk) => {
+                unsafe { &**back_chunk }.end == self.cap_per_chunk && chunk.chunk().start == 0
+            }
+            None => true,
+        };
+        if same_layout && !self.is_bounded() && !chunk.is_empty() && lines_up {
+            let len = chunk.len();
+            self.attach_back(chunk.into_raw());
+            self.size += len;
+            return None;
+        }
+        while chunk.chunk().len() > 0 {
+            let elem = chunk.chunk_mut().pop_front();
+            self.push_back(elem);
+        }
+        if same_layout && !self.is_bounded() {
+            self.recycle(chunk.into_raw());
+        }
+        None
+    }
+}
+
+impl<T: Sized> Drop for OwnedChunk<T> {
+    fn drop(&mut self) {
+        self.chunk_mut().drop_all();
+        Chunk::<T>::free(self.ptr, self.layout);
+    }
+}
+
+impl<T: Sized> Deref for OwnedChunk<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.chunk().as_slice()
+    }
+}
+
+impl<T: Sized> DerefMut for OwnedChunk<T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        let chunk = self.chunk_mut();
+        unsafe {
+            let elems = Chunk::elems(chunk).add(chunk.start as usize) as *mut T;
+            slice::from_raw_parts_mut(elems, chunk.len())
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for OwnedChunk<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+pub struct OwnedChunkIntoIter<T: Sized>(OwnedChunk<T>);
+
+impl<T: Sized> Iterator for OwnedChunkIntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let chunk = self.0.chunk_mut();
+        if chunk.len() > 0 {
+            Some(chunk.pop_front())
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let size = self.0.len();
+        (size, Some(size))
+    }
+}
+
+impl<T: Sized> DoubleEndedIterator for OwnedChunkIntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let chunk = self.0.chunk_mut();
+        if chunk.len() > 0 {
+            Some(chunk.pop_back())
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: Sized> ExactSizeIterator for OwnedChunkIntoIter<T> {}
+
+impl<T: Sized> FusedIterator for OwnedChunkIntoIter<T> {}
+
+impl<T: Sized> IntoIterator for OwnedChunk<T> {
+    type Item = T;
+    type IntoIter = OwnedChunkIntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        OwnedChunkIntoIter(self)
+    }
+}
+
+impl<'a, T: Sized> IntoIterator for &'a OwnedChunk<T> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T: Sized> IntoIterator for &'a mut OwnedChunk<T> {
+    type Item = &'a mut T;
+    type IntoIter = slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
@@ -0,0 +1,156 @@
+use crate::{chunk::Chunk, *};
+use core::{
+    fmt,
+    iter::FusedIterator,
+    ops::{Deref, DerefMut},
+    ptr,
+};
+
+impl<T> PinnedDeque<T>
+where
+    T: Sized,
+{
+    /// Pops the front element if `pred` returns true on it.
+    pub fn pop_front_if(&mut self, pred: impl FnOnce(&mut T) -> bool) -> Option<T> {
+        if pred(self.front_mut()?) {
+            self.pop_front()
+        } else {
+            None
+        }
+    }
+
+    /// Pops the back element if `pred` returns true on it.
+    pub fn pop_back_if(&mut self, pred: impl FnOnce(&mut T) -> bool) -> Option<T> {
+        if pred(self.back_mut()?) {
+            self.pop_back()
+        } else {
+            None
+        }
+    }
+
+    /// Returns a guard to the front element, which can pop it later.
+    pub fn front_peek_mut(&mut self) -> Option<PeekMut<'_, T>> {
+        if self.is_empty() {
+            return None;
+        }
+        self.unshare(0);
+        Some(PeekMut { deque: self })
+    }
+
+    /// Pops and drops elements at the front as long as `pred` returns true on them.
+    /// Returns how many elements are popped.
+    ///
+    /// Leading elements in a chunk are checked first, and then dropped all at once.
+    pub fn pop_front_while(&mut self, mut pred: impl FnMut(&mut T) -> bool) -> usize {
+        let mut cnt = 0;
+        while !self.used.is_empty() {
+            self.unshare(0);
+            let front_chunk_ptr = unsafe { *self.used.front().unwrap_unchecked() };
+            let front_chunk = unsafe { &mut *front_chunk_ptr };
+            let start = front_chunk.start;
+            let mut n = 0;
+            while start + n < front_chunk.end && pred(front_chunk.slot_mut((start + n) as usize)) {
+                n += 1;
+            }
+            // Bookkeeping goes first, so that nothing is dropped twice if a drop panics.
+            front_chunk.start += n;
+            self.size -= n as usize;
+            self.head_seq = self.head_seq.wrapping_add(n as u64);
+            cnt += n as usize;
+            let all_popped = front_chunk.len() == 0;
+            if all_popped {
+                self.detach_front();
+            }
+            unsafe {
+                let elems = Chunk::elems(front_chunk_ptr).add(start as usize) as *mut T;
+                ptr::drop_in_place(ptr::slice_from_raw_parts_mut(elems, n as usize));
+            }
+            if !all_popped {
+                break;
+            }
+            self.recycle(front_chunk_ptr);
+        }
+        cnt
+    }
+
+    /// Pops elements at the front lazily as long as `pred` returns true on them.
+    ///
+    /// Elements which are not yielded stay in the deque.
+    pub fn drain_front_while<F>(&mut self, pred: F) -> DrainFrontWhile<'_, T, F>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        DrainFrontWhile {
+            deque: self,
+            pred,
+            done: false,
+        }
+    }
+}
+
+/// A guard to the front element of a deque, returned by [`PinnedDeque::front_peek_mut()`].
+pub struct PeekMut<'a, T: Sized> {
+    deque: &'a mut PinnedDeque<T>,
+}
+
+impl<T: Sized> PeekMut<'_, T> {
+    /// Pops the element.
+    pub fn pop(this: Self) -> T {
+        unsafe { this.deque.pop_front().unwrap_unchecked() }
+    }
+}
+
+impl<T: Sized> Deref for PeekMut<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { self.deque.front().unwrap_unchecked() }
+    }
+}
+
+impl<T: Sized> DerefMut for PeekMut<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        let front_chunk = unsafe { &mut **self.deque.used.front().unwrap_unchecked() };
+        front_chunk.front_mut()
+    }
+}
+
+impl<T: Sized + fmt::Debug> fmt::Debug for PeekMut<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("PeekMut").field(&**self).finish()
+    }
+}
+
+/// The iterator returned by [`PinnedDeque::drain_front_while()`].
+pub struct DrainFrontWhile<'a, T: Sized, F> {
+    deque: &'a mut PinnedDeque<T>,
+    pred: F,
+    done: bool,
+}
+
+impl<T, F> Iterator for DrainFrontWhile<'_, T, F>
+where
+    T: Sized,
+    F: FnMut(&mut T) -> bool,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let res = self.deque.pop_front_if(&mut self.pred);
+        self.done = res.is_none();
+        res
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            (0, Some(0))
+        } else {
+            (0, Some(self.deque.len()))
+        }
+    }
+}
+
+impl<T: Sized, F: FnMut(&mut T) -> bool> FusedIterator for DrainFrontWhile<'_, T, F> {}
@@ -0,0 +1,65 @@
+use crate::*;
+use core::pin::Pin;
+use pinned_init::PinInit;
+
+impl<T> PinnedDeque<T>
+where
+    T: Sized,
+{
+    /// Pushes an element at the back, initializing it in place by `init`.
+    ///
+    /// If `init` fails, the slot is given back and the error is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the deque is full and the overflow policy makes no room.
+    ///
+    /// # Safety
+    ///
+    /// The element must stay where it is until it is dropped in place, i.e., by `clear()`,
+    /// `pop_front_while()` whose `pred` does not move it, or dropping the deque.
+    /// So none of the following may be used on it:
+    ///
+    /// - popping, e.g., `pop_back()`, `pop_front_if()`, `drain_front_while()` or
+    ///   `PeekMut::pop()`, and evicting elements from a bounded deque;
+    /// - mutable references, e.g., by `get_mut()` or `iter_mut()`, which let it be swapped out;
+    /// - iterating the deque by value, `Vec::from()`, `VecDeque::from()` and
+    ///   `into_boxed_slice()`;
+    /// - `append()` and `split_off()`, which move elements one by one unless chunks line up;
+    /// - `pop_front_chunk()` followed by `OwnedChunk::into_vec()` or iterating the chunk by
+    ///   value, and `push_back_chunk()` unless the chunk is linked as it is;
+    /// - snapshots, after which mutable access to the deque clones the element,
+    ///   and the returned reference no longer points to the one in the deque.
+    pub unsafe fn push_back_pin_init<E>(
+        &mut self,
+        init: impl PinInit<T, E>,
+    ) -> Result<Pin<&mut T>, E> {
+        let mut slot = self
+            .reserve_back_slot()
+            .expect("the deque is full and rejects the element");
+        init.__pinned_init(slot.as_mut_ptr())?;
+        Ok(Pin::new_unchecked(slot.assume_init()))
+    }
+
+    /// Pushes an element at the front, initializing it in place by `init`.
+    ///
+    /// If `init` fails, the slot is given back and the error is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the deque is full and the overflow policy makes no room.
+    ///
+    /// # Safety
+    ///
+    /// The same as `push_back_pin_init()`.
+    pub unsafe fn push_front_pin_init<E>(
+        &mut self,
+        init: impl PinInit<T, E>,
+    ) -> Result<Pin<&mut T>, E> {
+        let mut slot = self
+            .reserve_front_slot()
+            .expect("the deque is full and rejects the element");
+        init.__pinned_init(slot.as_mut_ptr())?;
+        Ok(Pin::new_unchecked(slot.assume_init()))
+    }
+}
@@ -0,0 +1,62 @@
+use crate::{chunk::Chunk, *};
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "std")]
+pub(crate) struct IdleRelease {
+    period: Duration,
+    last_fetch: Instant,
+}
+
+impl<T> PinnedDeque<T>
+where
+    T: Sized,
+{
+    /// Releases physical memory of spare chunks, but keeps them for later pushes.
+    /// Returns how many bytes are released.
+    ///
+    /// Spare chunks can be reused without memory allocations.
+    /// It is done by `madvise(MADV_DONTNEED)` on unix with `std`, and a no-op otherwise.
+    ///
+    /// Only whole pages in chunks are released.
+    /// So, chunks smaller than a page and not padded to it, e.g., with a small capacity per chunk,
+    /// keep their memory.
+    pub fn release_idle_memory(&mut self) -> usize {
+        for chunk in self.freed[self.released..].iter() {
+            Chunk::release_memory(*chunk, self.layout);
+        }
+        let n = self.freed.len() - self.released;
+        self.released = self.freed.len();
+        n * Chunk::<T>::releasable_size(self.layout)
+    }
+
+    /// Releases physical memory of spare chunks automatically,
+    /// if none of them is taken for `period`.
+    ///
+    /// It is checked when a chunk becomes spare, so an untouched deque keeps its memory.
+    /// `None` turns it off.
+    #[cfg(feature = "std")]
+    pub fn set_idle_release_period(&mut self, period: Option<Duration>) {
+        self.idle_release = period.map(|period| IdleRelease {
+            period,
+            last_fetch: Instant::now(),
+        });
+    }
+
+    #[cfg(feature = "std")]
+    pub(crate) fn touch_freed(&mut self) {
+        if let Some(idle_release) = self.idle_release.as_mut() {
+            idle_release.last_fetch = Instant::now();
+        }
+    }
+
+    #[cfg(feature = "std")]
+    pub(crate) fn release_memory_if_idle(&mut self) {
+        let Some(idle_release) = self.idle_release.as_ref() else {
+            return;
+        };
+        if idle_release.last_fetch.elapsed() >= idle_release.period {
+            self.release_idle_memory();
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use crate::*;
+
+/// Sequence numbers identify elements regardless of pops at the front.
+///
+/// The front element has sequence number `first_seq()`, and the following ones count up.
+/// `first_seq()` grows by one on every pop at the front,
+/// and goes down by one on every push at the front.
+/// All arithmetic on sequence numbers wraps around.
+impl<T> PinnedDeque<T>
+where
+    T: Sized,
+{
+    /// Returns the sequence number of the front element.
+    pub fn first_seq(&self) -> u64 {
+        self.head_seq
+    }
+
+    /// Returns the sequence number which the next element pushed at the back will have.
+    pub fn end_seq(&self) -> u64 {
+        self.head_seq.wrapping_add(self.len() as u64)
+    }
+
+    /// Pushes an element at the back, and returns its sequence number.
+    pub fn push_back_seq(&mut self, elem: T) -> u64 {
+        self.push_back(elem);
+        self.end_seq().wrapping_sub(1)
+    }
+
+    pub fn get_seq(&self, seq: u64) -> Option<&T> {
+        self.get(self.seq_to_index(seq)?)
+    }
+
+    pub fn get_seq_mut(&mut self, seq: u64) -> Option<&mut T> {
+        self.get_mut(self.seq_to_index(seq)?)
+    }
+
+    /// Iterates elements from the one with sequence number `seq` to the back.
+    ///
+    /// Returns `None` if `seq` is not in `first_seq()..=end_seq()`.
+    pub fn iter_from_seq(&self, seq: u64) -> Option<Iter<'_, T>> {
+        let idx = seq.wrapping_sub(self.head_seq);
+        if idx > self.len() as u64 {
+            return None;
+        }
+        Some(Iter::new_from(self, idx as usize))
+    }
+
+    fn seq_to_index(&self, seq: u64) -> Option<usize> {
+        let idx = seq.wrapping_sub(self.head_seq);
+        if idx < self.len() as u64 {
+            Some(idx as usize)
+        } else {
+            None
+        }
+    }
+}
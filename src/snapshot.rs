@@ -0,0 +1,357 @@
+use crate::{chunk::Chunk, *};
+use alloc::{alloc::Layout, collections::BTreeMap, sync::Arc, vec::Vec};
+use core::{fmt, iter::*, mem, ops::Range, ptr, slice};
+
+/// A chunk shared by a deque and its snapshots.
+///
+/// Elements in `range` are owned jointly, and they are dropped with the last reference.
+/// Nobody writes into a shared chunk, but the deque may still move `start` and `end`
+/// in its header on pops.
+pub(crate) struct SharedChunk<T: Sized> {
+    chunk: *mut Chunk<T>,
+    range: Range<u32>,
+    layout: Layout,
+}
+
+unsafe impl<T: Sized + Send + Sync> Send for SharedChunk<T> {}
+
+unsafe impl<T: Sized + Send + Sync> Sync for SharedChunk<T> {}
+
+impl<T: Sized> Drop for SharedChunk<T> {
+    fn drop(&mut self) {
+        unsafe {
+            let elems = Chunk::elems(self.chunk).add(self.range.start as usize) as *mut T;
+            let len = (self.range.end - self.range.start) as usize;
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(elems, len));
+        }
+        Chunk::free(self.chunk, self.layout);
+    }
+}
+
+impl<T: Sized> SharedChunk<T> {
+    /// Gives the chunk back to the deque, when no snapshot refers to it any more.
+    ///
+    /// Elements in `range` but popped from the deque since are dropped.
+    fn reclaim(self) {
+        unsafe {
+            let header = &*self.chunk;
+            debug_assert!(self.range.start <= header.start && header.end <= self.range.end);
+            let elems = Chunk::elems(self.chunk) as *mut T;
+            let popped_front = (header.start - self.range.start) as usize;
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(
+                elems.add(self.range.start as usize),
+                popped_front,
+            ));
+            let popped_back = (self.range.end - header.end) as usize;
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(
+                elems.add(header.end as usize),
+                popped_back,
+            ));
+        }
+        mem::forget(self);
+    }
+}
+
+pub(crate) type SharedChunks<T> = BTreeMap<usize, Arc<SharedChunk<T>>>;
+
+pub(crate) type Cloner<T> = fn(&T) -> T;
+
+/// A read-only snapshot of a [`PinnedDeque`].
+///
+/// It shares chunks with the deque, rather than copying elements.
+pub struct PinnedDequeSnapshot<T: Sized> {
+    size: usize,
+    cap_per_chunk: usize,
+    chunks: Vec<(Arc<SharedChunk<T>>, Range<u32>)>,
+}
+
+impl<T> PinnedDeque<T>
+where
+    T: Sized + Clone,
+{
+    /// Takes a snapshot of the deque in O(number of chunks) time.
+    ///
+    /// Chunks are shared by the deque and its snapshots until the deque writes into them.
+    /// Before that, the deque copies a shared chunk, i.e., on pushes into the front or the back
+    /// chunk which is partially filled, or on mutable accesses.
+    ///
+    /// Caveat:
+    /// Copying a chunk moves elements in it.
+    /// So, elements are pinned only when chunks holding them are not shared.
+    /// A chunk is not shared any more once all snapshots referring to it are dropped.
+    pub fn snapshot(&self) -> PinnedDequeSnapshot<T> {
+        self.cloner.set(Some(T::clone));
+        let mut shared = self.shared.borrow_mut();
+        let chunks = self
+            .used
+            .iter()
+            .map(|chunk| {
+                let header = unsafe { &*(*chunk as *const Chunk<T>) };
+                let range = header.start..header.end;
+                let shared_chunk = shared.entry(*chunk as usize).or_insert_with(|| {
+                    Arc::new(SharedChunk {
+                        chunk: *chunk,
+                        range: range.clone(),
+                        layout: self.layout,
+                    })
+                });
+                (shared_chunk.clone(), range)
+            })
+            .collect();
+        PinnedDequeSnapshot {
+            size: self.len(),
+            cap_per_chunk: self.cap_per_chunk as usize,
+            chunks,
+        }
+    }
+}
+
+impl<T> PinnedDeque<T>
+where
+    T: Sized,
+{
+    /// Returns whether snapshots refer to the chunk.
+    ///
+    /// If only the deque does, the chunk is reclaimed, and it is not shared any more.
+    pub(crate) fn is_shared(&mut self, chunk: *mut Chunk<T>) -> bool {
+        let shared = self.shared.get_mut();
+        if shared.is_empty() {
+            return false;
+        }
+        let addr = chunk as usize;
+        match shared.get(&addr) {
+            None => false,
+            Some(shared_chunk) if Arc::strong_count(shared_chunk) > 1 => true,
+            Some(_) => {
+                let shared_chunk = unsafe { shared.remove(&addr).unwrap_unchecked() };
+                match Arc::try_unwrap(shared_chunk) {
+                    Ok(shared_chunk) => {
+                        shared_chunk.reclaim();
+                        false
+                    }
+                    Err(shared_chunk) => {
+                        shared.insert(addr, shared_chunk);
+                        true
+                    }
+                }
+            }
+        }
+    }
+
+    /// Clones an element in a shared chunk.
+    pub(crate) fn clone_shared(&self, elem: &T) -> T {
+        let cloner = unsafe { self.cloner.get().unwrap_unchecked() };
+        cloner(elem)
+    }
+
+    /// Drops the reference to a chunk, if it is shared.
+    ///
+    /// Returns whether snapshots still refer to it, i.e., the deque gives it up.
+    pub(crate) fn release_shared(&mut self, chunk: *mut Chunk<T>) -> bool {
+        if !self.is_shared(chunk) {
+            return false;
+        }
+        self.give_up_shared(chunk);
+        true
+    }
+
+    /// Drops the reference to a chunk which is known to be shared, and gives it up.
+    ///
+    /// It does not check `is_shared()` again, because the last snapshot may be dropped
+    /// on another thread in the meantime, and then the chunk would be reclaimed instead.
+    pub(crate) fn give_up_shared(&mut self, chunk: *mut Chunk<T>) {
+        self.shared.get_mut().remove(&(chunk as usize));
+        self.forget_chunk(chunk);
+    }
+
+    /// Copies the back chunk before pushing into it, if it is shared.
+    pub(crate) fn unshare_back_for_push(&mut self) {
+        let Some(back_chunk) = self.used.back() else {
+            return;
+        };
+        let back_chunk = *back_chunk;
+        if self.is_shared(back_chunk) && unsafe { &*back_chunk }.end < self.cap_per_chunk {
+            self.unshare(self.used.len() - 1);
+        }
+    }
+
+    /// Copies the front chunk before pushing into it, if it is shared.
+    pub(crate) fn unshare_front_for_push(&mut self) {
+        let Some(front_chunk) = self.used.front() else {
+            return;
+        };
+        let front_chunk = *front_chunk;
+        if self.is_shared(front_chunk) && unsafe { &*front_chunk }.start > 0 {
+            self.unshare(0);
+        }
+    }
+
+    pub(crate) fn unshare_all(&mut self) {
+        if self.shared.get_mut().is_empty() {
+            return;
+        }
+        for n in 0..self.used.len() {
+            self.unshare(n);
+        }
+    }
+
+    /// Copies the `n`-th used chunk, if it is shared.
+    pub(crate) fn unshare(&mut self, n: usize) {
+        let chunk = self.used[n];
+        if !self.is_shared(chunk) {
+            return;
+        }
+        let new_chunk = self.fetch_a_freed_chunk();
+        unsafe {
+            let old = &*chunk;
+            let new = &mut *new_chunk;
+            new.start = old.start;
+            new.end = old.start;
+            for slot in old.start..old.end {
+                let elem = self.clone_shared(old.slot(slot as usize));
+                new.reserve_back(self.cap_per_chunk)
+                    .unwrap_unchecked()
+                    .write(elem);
+            }
+        }
+        self.replace_used(n, new_chunk);
+        self.give_up_shared(chunk);
+    }
+}
+
+impl<T> PinnedDequeSnapshot<T>
+where
+    T: Sized,
+{
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        self.get(self.len().wrapping_sub(1))
+    }
+
+    pub fn get(&self, idx: usize) -> Option<&T> {
+        if idx >= self.len() {
+            return None;
+        }
+        let (_, first_range) = unsafe { self.chunks.first().unwrap_unchecked() };
+        let idx = idx + first_range.start as usize;
+        let (chunk, _) = unsafe { self.chunks.get_unchecked(idx / self.cap_per_chunk) };
+        let slot = idx % self.cap_per_chunk;
+        Some(unsafe { &*Chunk::elems(chunk.chunk).add(slot) })
+    }
+
+    pub fn iter(&self) -> SnapshotIter<'_, T> {
+        SnapshotIter {
+            size: self.size,
+            chunk_iter: self.chunks.iter(),
+            front: [].iter(),
+            back: [].iter(),
+        }
+    }
+}
+
+impl<T> Clone for PinnedDequeSnapshot<T>
+where
+    T: Sized,
+{
+    fn clone(&self) -> Self {
+        Self {
+            size: self.size,
+            cap_per_chunk: self.cap_per_chunk,
+            chunks: self.chunks.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for PinnedDequeSnapshot<T>
+where
+    T: Sized + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[derive(Clone)]
+pub struct SnapshotIter<'a, T: Sized> {
+    size: usize,
+    chunk_iter: slice::Iter<'a, (Arc<SharedChunk<T>>, Range<u32>)>,
+    front: slice::Iter<'a, T>,
+    back: slice::Iter<'a, T>,
+}
+
+fn shared_slice<T: Sized>((chunk, range): &(Arc<SharedChunk<T>>, Range<u32>)) -> &[T] {
+    unsafe {
+        let elems = Chunk::elems(chunk.chunk).add(range.start as usize);
+        slice::from_raw_parts(elems, (range.end - range.start) as usize)
+    }
+}
+
+impl<'a, T> Iterator for SnapshotIter<'a, T>
+where
+    T: Sized,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(res) = self.front.next() {
+                self.size -= 1;
+                return Some(res);
+            }
+            if let Some(chunk) = self.chunk_iter.next() {
+                self.front = shared_slice(chunk).iter();
+            } else {
+                let res = self.back.next()?;
+                self.size -= 1;
+                return Some(res);
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.size, Some(self.size))
+    }
+}
+
+impl<T> DoubleEndedIterator for SnapshotIter<'_, T>
+where
+    T: Sized,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(res) = self.back.next_back() {
+                self.size -= 1;
+                return Some(res);
+            }
+            if let Some(chunk) = self.chunk_iter.next_back() {
+                self.back = shared_slice(chunk).iter();
+            } else {
+                let res = self.front.next_back()?;
+                self.size -= 1;
+                return Some(res);
+            }
+        }
+    }
+}
+
+impl<T: Sized> ExactSizeIterator for SnapshotIter<'_, T> {}
+
+impl<'a, T: Sized> IntoIterator for &'a PinnedDequeSnapshot<T> {
+    type Item = &'a T;
+    type IntoIter = SnapshotIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
@@ -0,0 +1,130 @@
+use crate::{chunk::Chunk, *};
+
+impl<T> PinnedDeque<T>
+where
+    T: Sized,
+{
+    /// Moves all elements of `other` to the back of `self`, leaving `other` empty.
+    ///
+    /// If both deques have the same capacity per chunk, chunks of `other` are handed over
+    /// as they are, and elements in them stay pinned.
+    /// Only when the back chunk of `self` is partially filled, elements in the front chunk of
+    /// `other` are moved to fill it up, provided that they line up with its free slots.
+    /// If they do not, elements of the shorter deque are moved one by one to line chunks up,
+    /// e.g., only those in the back chunk of `self` if it is the only chunk.
+    /// Otherwise, e.g., if the deques differ in capacity per chunk, or `self` is bounded,
+    /// all elements of `other` are moved one by one.
+    ///
+    /// A bounded `self` follows its overflow policy for each element, as `push_back()` does,
+    /// except that under [`OverflowPolicy::Reject`] elements which do not fit stay in `other`.
+    pub fn append(&mut self, other: &mut Self) {
+        if other.is_empty() {
+            return;
+        }
+        if self.is_bounded() || self.cap_per_chunk != other.cap_per_chunk {
+            self.move_all_from(other);
+            return;
+        }
+        if let Some(back_chunk) = self.used.back() {
+            let back_end = unsafe { &**back_chunk }.end;
+            let front_start = unsafe { &**other.used.front().unwrap_unchecked() }.start;
+            if back_end == front_start {
+                // Fills up the back chunk with the front chunk of `other`.
+                let n = unsafe { &**other.used.front().unwrap_unchecked() }.len();
+                for _ in 0..n {
+                    let elem = unsafe { other.pop_front().unwrap_unchecked() };
+                    self.push_back_unbounded(elem);
+                }
+            } else if back_end != self.cap_per_chunk || front_start != 0 {
+                if other.len() <= self.len() {
+                    self.move_all_from(other);
+                    return;
+                }
+                // `self` becomes empty, so that chunks of `other` line up with no chunk.
+                self.move_all_to_front_of(other);
+            }
+        }
+        let mut n = 0;
+        while let Some(chunk) = other.detach_front() {
+            n += unsafe { &*chunk }.len();
+            other.hand_over_shared(self, chunk);
+            other.forget_chunk(chunk);
+            self.attach_back(chunk);
+        }
+        self.size += n;
+        other.size -= n;
+        other.head_seq = other.head_seq.wrapping_add(n as u64);
+    }
+
+    /// Splits the deque into two at `at`.
+    /// Returns a new deque with elements in `[at, len)`, and `self` keeps those in `[0, at)`.
+    ///
+    /// The new deque has the same capacity per chunk.
+    /// Whole chunks after `at` are handed over, and elements in them stay pinned.
+    /// Only elements in the same chunk as the element at `at` are moved,
+    /// unless `at` lands on a chunk boundary.
+    /// Elements keep their sequence numbers in the new deque.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at > len`.
+    pub fn split_off(&mut self, at: usize) -> Self {
+        assert!(at <= self.len(), "`at` out of bounds");
+        let mut other = Self::with_capacity_per_chunk(self.cap_per_chunk);
+        let Some((n, slot)) = self.locate_in_used(at) else {
+            other.head_seq = self.head_seq.wrapping_add(at as u64);
+            return other;
+        };
+        let chunk_n = unsafe { &*(self.used[n] as *const Chunk<T>) };
+        let on_boundary = slot == chunk_n.start as usize;
+        // The chunk holding the element at `at` is handed over as well, if it starts there.
+        let keep = if on_boundary { n } else { n + 1 };
+        let mut moved = 0;
+        while self.used.len() > keep {
+            let chunk = unsafe { self.detach_back().unwrap_unchecked() };
+            moved += unsafe { &*chunk }.len();
+            self.hand_over_shared(&mut other, chunk);
+            self.forget_chunk(chunk);
+            other.attach_front(chunk);
+        }
+        self.size -= moved;
+        other.size += moved;
+        // The rest of the chunk lines up with the chunks handed over, as long as it is full.
+        for _ in 0..(self.len() - at) {
+            let elem = unsafe { self.pop_back().unwrap_unchecked() };
+            other.push_front_unbounded(elem);
+        }
+        other.head_seq = self.head_seq.wrapping_add(at as u64);
+        other
+    }
+
+    /// Stops once `self` is full, if it rejects overflowing elements.
+    fn move_all_from(&mut self, other: &mut Self) {
+        while !(self.is_full() && self.rejects_overflow()) {
+            let Some(elem) = other.pop_front() else {
+                break;
+            };
+            self.push_back(elem);
+        }
+    }
+
+    /// Moves all elements of `self` to the front of `other`, which is only taken from then.
+    fn move_all_to_front_of(&mut self, other: &mut Self) {
+        while let Some(elem) = self.pop_back() {
+            other.push_front_unbounded(elem);
+        }
+    }
+
+    /// Hands over the reference to a chunk shared with snapshots, if any,
+    /// before the chunk moves from `self` to `other`.
+    fn hand_over_shared(&mut self, other: &mut Self, chunk: *mut Chunk<T>) {
+        let shared = self.shared.get_mut();
+        if shared.is_empty() {
+            return;
+        }
+        if let Some(shared_chunk) = shared.remove(&(chunk as usize)) {
+            other.shared.get_mut().insert(chunk as usize, shared_chunk);
+            other.cloner.set(self.cloner.get());
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use crate::{chunk::Chunk, *};
+use alloc::vec::Vec;
+use core::{mem::size_of, ops::Range};
+
+/// Bytes held by a deque, broken down by where they are held.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MemoryUsage {
+    /// Bytes in chunks which hold at least one element.
+    pub used_chunks: usize,
+    /// Bytes in spare chunks kept for later pushes.
+    pub freed_chunks: usize,
+    /// Bytes released from spare chunks, i.e., whole pages in them.
+    /// They are included in `freed_chunks` as well.
+    pub released_chunks: usize,
+    /// Bytes in the containers tracking chunks.
+    pub bookkeeping: usize,
+}
+
+impl MemoryUsage {
+    pub fn total(&self) -> usize {
+        self.used_chunks + self.freed_chunks + self.bookkeeping
+    }
+}
+
+/// Statistics about chunks of a deque.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ChunkStats {
+    pub used_chunks: usize,
+    pub free_chunks: usize,
+    pub cap_per_chunk: usize,
+    /// Unused slots before the first element in the first chunk.
+    pub front_slack: usize,
+    /// Unused slots after the last element in the last chunk.
+    pub back_slack: usize,
+}
+
+impl<T> PinnedDeque<T>
+where
+    T: Sized,
+{
+    /// Returns how many bytes this deque holds.
+    ///
+    /// Memory held by elements themselves (e.g., the buffer of a `String`) is not counted.
+    pub fn memory_usage(&self) -> MemoryUsage {
+        let chunk_size = self.layout.size();
+        let ptr_size = size_of::<*mut Chunk<T>>();
+        MemoryUsage {
+            used_chunks: self.used.len() * chunk_size,
+            freed_chunks: self.freed.len() * chunk_size,
+            released_chunks: self.released * Chunk::<T>::releasable_size(self.layout),
+            bookkeeping: (self.used.capacity() + self.freed.capacity()) * ptr_size
+                + self.chunk_seqs.capacity() * size_of::<(usize, isize)>(),
+        }
+    }
+
+    pub fn stats(&self) -> ChunkStats {
+        let cap_per_chunk = self.cap_per_chunk as usize;
+        let front_slack = self.used.front().map_or(0, |chunk| {
+            let chunk = unsafe { &*(*chunk as *const Chunk<T>) };
+            chunk.start as usize
+        });
+        let back_slack = self.used.back().map_or(0, |chunk| {
+            let chunk = unsafe { &*(*chunk as *const Chunk<T>) };
+            cap_per_chunk - chunk.end as usize
+        });
+        ChunkStats {
+            used_chunks: self.used.len(),
+            free_chunks: self.freed.len(),
+            cap_per_chunk,
+            front_slack,
+            back_slack,
+        }
+    }
+
+    /// Lists occupied slots, i.e., `start..end`, of every used chunk from front to back.
+    ///
+    /// This is intended for debugging.
+    pub fn layout(&self) -> Vec<Range<usize>> {
+        self.used
+            .iter()
+            .map(|chunk| {
+                let chunk = unsafe { &*(*chunk as *const Chunk<T>) };
+                (chunk.start as usize)..(chunk.end as usize)
+            })
+            .collect()
+    }
+}
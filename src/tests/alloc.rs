@@ -0,0 +1,119 @@
+use crate::*;
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+/// Counts allocations made by the current thread.
+struct CountingAlloc;
+
+std::thread_local! {
+    static ALLOCS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCS.try_with(|n| n.set(n.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCS.try_with(|n| n.set(n.get() + 1));
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let _ = ALLOCS.try_with(|n| n.set(n.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+/// Returns how many allocations `f` makes.
+pub(super) fn count_allocs(f: impl FnOnce()) -> usize {
+    let before = ALLOCS.with(Cell::get);
+    f();
+    ALLOCS.with(Cell::get) - before
+}
+
+#[test]
+fn try_push() {
+    let mut trial = PinnedDeque::<usize>::with_capacity_per_chunk(2);
+    trial.try_push_back(1).unwrap();
+    trial.try_push_front(0).unwrap();
+    trial.try_push_back(2).unwrap();
+    assert_eq!(trial.len(), 3);
+    assert_eq!(format!("{trial:?}"), "[0, 1, 2]");
+}
+
+#[test]
+fn try_reserve() {
+    let mut trial = PinnedDeque::<usize>::with_capacity_per_chunk(2);
+    trial.try_reserve(5).unwrap();
+    assert_eq!(trial.capacity(), 6);
+}
+
+#[test]
+fn try_extend() {
+    let mut trial = PinnedDeque::<usize>::with_capacity_per_chunk(2);
+    trial.try_extend(0..5).unwrap();
+    assert_eq!(format!("{trial:?}"), "[0, 1, 2, 3, 4]");
+}
+
+#[test]
+fn try_reserve_overflowing_capacity() {
+    let mut trial = PinnedDeque::<usize>::with_capacity_per_chunk(2);
+    let err = trial.try_reserve(usize::MAX).unwrap_err();
+    assert_eq!(err.layout(), None);
+    assert_eq!(trial.capacity(), 0);
+}
+
+#[test]
+fn try_push_error_gives_elem_back() {
+    // Every chunk takes 2^47 bytes, more than a user address space.
+    let mut trial = PinnedDeque::<[u8; 1 << 16]>::with_capacity_per_chunk(1 << 31);
+    let err = trial.try_push_back([7; 1 << 16]).unwrap_err();
+    assert!(err.error().unwrap().layout().unwrap().size() >= 1 << 47);
+    assert!(err.element().iter().all(|&b| b == 7));
+    assert!(trial.is_empty());
+    assert!(trial.try_reserve(1).is_err());
+}
+
+#[test]
+fn bounded_never_allocates() {
+    let mut trial =
+        PinnedDeque::<usize>::bounded_with_capacity_per_chunk(400, OverflowPolicy::EvictFront, 4);
+    let allocs = count_allocs(|| {
+        for i in 0..4000 {
+            trial.push_back(i);
+            assert_eq!(trial.index_of(trial.back().unwrap()), Some(trial.len() - 1));
+        }
+        for i in 0..4000 {
+            trial.push_front(i);
+            trial.pop_back();
+        }
+    });
+    assert_eq!(allocs, 0);
+}
+
+#[test]
+fn reserved_never_allocates() {
+    let mut trial = PinnedDeque::<usize>::with_capacity_per_chunk(4);
+    trial.reserve(400);
+    let allocs = count_allocs(|| {
+        for i in 0..400 {
+            trial.push_back(i);
+        }
+        while trial.pop_front().is_some() {}
+        for i in 0..400 {
+            trial.push_front(i);
+        }
+    });
+    assert_eq!(allocs, 0);
+}
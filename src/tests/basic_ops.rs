@@ -185,3 +185,98 @@ fn front_mut() {
     let trial: Vec<_> = trial.iter().copied().collect();
     assert_eq!(trial, vec![1]);
 }
+
+#[quickcheck]
+fn get_by_div(ops: Vec<Op>) {
+    check_get(PinnedDeque::with_capacity_per_chunk(3), ops);
+}
+
+#[quickcheck]
+fn get_by_shift(ops: Vec<Op>) {
+    check_get(PinnedDeque::with_pow2_capacity_per_chunk(3), ops);
+}
+
+fn check_get(mut trial: PinnedDeque<usize>, ops: Vec<Op>) {
+    let mut oracle = VecDeque::new();
+    for op in ops.into_iter() {
+        match op {
+            Op::PopBack => {
+                assert_eq!(trial.pop_back(), oracle.pop_back());
+            }
+            Op::PopFront => {
+                assert_eq!(trial.pop_front(), oracle.pop_front());
+            }
+            Op::PushBack(n) => {
+                oracle.push_back(n);
+                trial.push_back(n);
+            }
+            Op::PushFront(n) => {
+                oracle.push_front(n);
+                trial.push_front(n);
+            }
+        }
+    }
+    for idx in 0..=oracle.len() {
+        assert_eq!(trial.get(idx), oracle.get(idx));
+        assert_eq!(trial.get_mut(idx), oracle.get_mut(idx));
+    }
+}
+
+#[test]
+fn pow2_capacity_per_chunk() {
+    let trial = PinnedDeque::<usize>::with_pow2_capacity_per_chunk(3);
+    assert_eq!(trial.stats().cap_per_chunk, 4);
+    let trial = PinnedDeque::<usize>::with_pow2_capacity_per_chunk(4);
+    assert_eq!(trial.stats().cap_per_chunk, 4);
+}
+
+#[quickcheck]
+fn index_of(ops: Vec<Op>) {
+    let mut trial = PinnedDeque::<usize>::with_capacity_per_chunk(3);
+    for op in ops.into_iter() {
+        match op {
+            Op::PopBack => {
+                let _ = trial.pop_back();
+            }
+            Op::PopFront => {
+                let _ = trial.pop_front();
+            }
+            Op::PushBack(n) => {
+                trial.push_back(n);
+            }
+            Op::PushFront(n) => {
+                trial.push_front(n);
+            }
+        }
+        for (idx, elem) in trial.iter().enumerate() {
+            assert_eq!(trial.index_of(elem), Some(idx));
+        }
+    }
+    let other = PinnedDeque::<usize>::from([0]);
+    assert_eq!(trial.index_of(other.front().unwrap()), None);
+}
+
+#[test]
+fn contains_ptr() {
+    let mut trial = PinnedDeque::<u64>::with_capacity_per_chunk(4);
+    trial.push_back(0);
+    trial.push_back(1);
+    let ptr: *const u64 = trial.front().unwrap();
+    assert!(trial.contains_ptr(ptr));
+    assert!(trial.contains_ptr(ptr.wrapping_add(1)));
+    assert!(!trial.contains_ptr(ptr.wrapping_add(2)));
+    assert!(!trial.contains_ptr((ptr as *const u8).wrapping_add(1) as *const u64));
+    trial.pop_front();
+    assert!(!trial.contains_ptr(ptr));
+    // The chunk is kept for later pushes, but its slots are no longer in the deque.
+    trial.pop_front();
+    assert!(!trial.contains_ptr(ptr.wrapping_add(1)));
+}
+
+#[test]
+fn index_of_zero_sized() {
+    let mut trial = PinnedDeque::<()>::with_capacity_per_chunk(4);
+    trial.push_back(());
+    trial.push_back(());
+    assert_eq!(trial.index_of(trial.front().unwrap()), None);
+}
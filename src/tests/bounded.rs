@@ -0,0 +1,163 @@
+use super::*;
+use crate::*;
+use quickcheck_macros::quickcheck;
+use std::collections::VecDeque;
+
+#[quickcheck]
+fn reject(ops: Vec<Op>, max_len: u8) {
+    check(ops, max_len as usize, OverflowPolicy::Reject);
+}
+
+#[quickcheck]
+fn evict_front(ops: Vec<Op>, max_len: u8) {
+    check(ops, max_len as usize, OverflowPolicy::EvictFront);
+}
+
+#[quickcheck]
+fn evict_back(ops: Vec<Op>, max_len: u8) {
+    check(ops, max_len as usize, OverflowPolicy::EvictBack);
+}
+
+fn check(ops: Vec<Op>, max_len: usize, policy: OverflowPolicy) {
+    let mut oracle = VecDeque::new();
+    let mut trial = PinnedDeque::<usize>::bounded_with_capacity_per_chunk(max_len, policy, 2);
+    let chunks = trial.stats().free_chunks;
+    for op in ops.into_iter() {
+        match op {
+            Op::PopBack => {
+                assert_eq!(trial.pop_back(), oracle.pop_back());
+            }
+            Op::PopFront => {
+                assert_eq!(trial.pop_front(), oracle.pop_front());
+            }
+            Op::PushBack(n) => {
+                let overflowed = if oracle.len() < max_len {
+                    oracle.push_back(n);
+                    None
+                } else {
+                    oracle_overflow(&mut oracle, policy, n, VecDeque::push_back)
+                };
+                assert_eq!(trial.push_back_bounded(n), overflowed);
+            }
+            Op::PushFront(n) => {
+                let overflowed = if oracle.len() < max_len {
+                    oracle.push_front(n);
+                    None
+                } else {
+                    oracle_overflow(&mut oracle, policy, n, VecDeque::push_front)
+                };
+                assert_eq!(trial.push_front_bounded(n), overflowed);
+            }
+        }
+        assert_eq!(trial.len(), oracle.len());
+        assert_eq!(trial.is_full(), oracle.len() == max_len);
+        assert_eq!(trial.remaining(), max_len - oracle.len());
+        let stats = trial.stats();
+        assert_eq!(stats.used_chunks + stats.free_chunks, chunks);
+    }
+    let trial: VecDeque<_> = trial.iter().copied().collect();
+    assert_eq!(trial, oracle);
+}
+
+fn oracle_overflow(
+    oracle: &mut VecDeque<usize>,
+    policy: OverflowPolicy,
+    elem: usize,
+    push: fn(&mut VecDeque<usize>, usize),
+) -> Option<usize> {
+    let evicted = match policy {
+        OverflowPolicy::Reject => return Some(elem),
+        OverflowPolicy::EvictFront => oracle.pop_front(),
+        OverflowPolicy::EvictBack => oracle.pop_back(),
+        OverflowPolicy::Panic => unreachable!(),
+    };
+    if evicted.is_some() {
+        push(oracle, elem);
+        evicted
+    } else {
+        Some(elem)
+    }
+}
+
+#[test]
+#[should_panic]
+fn panic() {
+    let mut trial = PinnedDeque::<usize>::bounded(1, OverflowPolicy::Panic);
+    trial.push_back(0);
+    trial.push_back(1);
+}
+
+#[test]
+fn extend() {
+    let mut trial = PinnedDeque::<usize>::bounded(3, OverflowPolicy::EvictFront);
+    assert_eq!(trial.max_len(), Some(3));
+    trial.extend(0..10);
+    assert_eq!(format!("{trial:?}"), "[7, 8, 9]");
+    trial.clear();
+    assert_eq!(trial.remaining(), 3);
+}
+
+#[test]
+fn unbounded() {
+    let trial = PinnedDeque::<usize>::new();
+    assert_eq!(trial.max_len(), None);
+    assert!(!trial.is_full());
+}
+
+#[test]
+fn try_push_gives_rejected_elem_back() {
+    let mut trial =
+        PinnedDeque::<String>::bounded_with_capacity_per_chunk(2, OverflowPolicy::Reject, 2);
+    trial.try_push_back("a".to_owned()).unwrap();
+    trial.try_push_back("b".to_owned()).unwrap();
+    let err = trial.try_push_back("c".to_owned()).unwrap_err();
+    assert!(err.is_full());
+    assert_eq!(err.into_element(), "c");
+    let err = trial.try_push_front("d".to_owned()).unwrap_err();
+    assert!(err.is_full());
+    assert_eq!(err.into_element(), "d");
+    assert_eq!(trial, ["a", "b"]);
+
+    let mut trial =
+        PinnedDeque::<String>::bounded_with_capacity_per_chunk(2, OverflowPolicy::EvictFront, 2);
+    trial.try_extend(["a", "b", "c"].map(String::from)).unwrap();
+    assert_eq!(trial, ["b", "c"]);
+}
+
+#[test]
+fn push_into_full_deque_drops_rejected_elem() {
+    let mut trial = PinnedDeque::<String>::bounded(2, OverflowPolicy::Reject);
+    for x in ["a", "b", "c"] {
+        trial.push_back(x.to_owned());
+    }
+    trial.push_front("d".to_owned());
+    assert_eq!(trial, ["a", "b"]);
+    trial.extend(["e", "f"].map(String::from));
+    assert_eq!(trial, ["a", "b"]);
+
+    let mut trial = PinnedDeque::<String>::bounded(0, OverflowPolicy::EvictFront);
+    trial.push_back("a".to_owned());
+    trial.push_front("b".to_owned());
+    trial.extend(["c".to_owned()]);
+    assert!(trial.is_empty());
+}
+
+#[test]
+fn append_into_full_deque() {
+    let mut trial = PinnedDeque::<String>::bounded(3, OverflowPolicy::Reject);
+    trial.extend(["a", "b"].map(String::from));
+    let mut other = PinnedDeque::from(["c", "d", "e"].map(String::from));
+    trial.append(&mut other);
+    assert_eq!(trial, ["a", "b", "c"]);
+    // Elements which do not fit are left in `other`.
+    assert_eq!(other, ["d", "e"]);
+    trial.append(&mut other);
+    assert_eq!(trial, ["a", "b", "c"]);
+    assert_eq!(other, ["d", "e"]);
+
+    let mut trial = PinnedDeque::<String>::bounded(3, OverflowPolicy::EvictFront);
+    trial.extend(["a", "b", "c"].map(String::from));
+    trial.append(&mut other);
+    assert_eq!(trial, ["c", "d", "e"]);
+    assert!(other.is_empty());
+}
@@ -0,0 +1,67 @@
+use crate::*;
+use bytes::{Buf, BufMut};
+use quickcheck_macros::quickcheck;
+
+#[quickcheck]
+fn put_and_get(xs: Vec<Vec<u8>>, ys: Vec<u64>) {
+    let mut trial = PinnedDeque::<u8>::with_capacity_per_chunk(5);
+    let mut oracle = vec![];
+    for x in xs.iter() {
+        trial.writer().put_slice(x);
+        oracle.extend_from_slice(x);
+    }
+    for y in ys.iter() {
+        trial.writer().put_u64(*y);
+        oracle.put_u64(*y);
+    }
+    assert!(trial.iter().eq(oracle.iter()));
+    let mut reader = trial.reader();
+    assert_eq!(reader.remaining(), oracle.len());
+    let mut head = vec![0; xs.iter().map(Vec::len).sum()];
+    reader.copy_to_slice(&mut head);
+    for y in ys.iter() {
+        assert_eq!(reader.get_u64(), *y);
+    }
+    assert!(!reader.has_remaining());
+    assert!(trial.is_empty());
+}
+
+#[test]
+fn chunks() {
+    let mut trial = PinnedDeque::<u8>::with_capacity_per_chunk(4);
+    trial.writer().put_slice(b"abcdefghij");
+    let mut reader = trial.reader();
+    assert_eq!(reader.chunk(), b"abcd");
+    reader.advance(3);
+    assert_eq!(reader.chunk(), b"d");
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn chunks_vectored() {
+    let mut trial = PinnedDeque::<u8>::with_capacity_per_chunk(4);
+    trial.writer().put_slice(b"abcdefghij");
+    let mut reader = trial.reader();
+    reader.advance(3);
+    let mut slices = [std::io::IoSlice::new(&[]); 4];
+    assert_eq!(reader.chunks_vectored(&mut slices), 3);
+    assert_eq!(&*slices[0], b"d");
+    assert_eq!(&*slices[1], b"efgh");
+    assert_eq!(&*slices[2], b"ij");
+}
+
+#[test]
+fn writer_into_bounded_deque() {
+    let mut trial =
+        PinnedDeque::<u8>::bounded_with_capacity_per_chunk(6, OverflowPolicy::Reject, 4);
+    let mut writer = trial.writer();
+    assert_eq!(writer.remaining_mut(), 6);
+    writer.put_slice(b"abcd");
+    assert_eq!(writer.remaining_mut(), 2);
+    assert_eq!(writer.chunk_mut().len(), 2);
+    writer.put_slice(b"ef");
+    assert!(!writer.has_remaining_mut());
+    assert_eq!(writer.chunk_mut().len(), 0);
+    unsafe { writer.advance_mut(1) };
+    assert_eq!(trial, b"abcdef".as_slice());
+}
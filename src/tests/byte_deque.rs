@@ -0,0 +1,138 @@
+use crate::*;
+use quickcheck_macros::quickcheck;
+use std::{
+    collections::VecDeque,
+    io::{BufRead, Read, Write},
+};
+
+#[quickcheck]
+fn write_and_read(writes: Vec<Vec<u8>>, reads: Vec<u8>) {
+    let mut oracle: VecDeque<u8> = VecDeque::new();
+    let mut trial = PinnedByteDeque::with_capacity_per_chunk(7);
+    let mut reads = reads.into_iter();
+    for w in writes.iter() {
+        trial.write_all(w).unwrap();
+        oracle.extend(w.iter());
+        if let Some(r) = reads.next() {
+            let mut buf = vec![0; r as usize % 20];
+            let n = trial.read(&mut buf).unwrap();
+            assert_eq!(n, buf.len().min(oracle.len()));
+            let expected: Vec<_> = oracle.drain(..n).collect();
+            assert_eq!(&buf[..n], &expected[..]);
+        }
+        assert_eq!(trial.len(), oracle.len());
+        assert!(trial.iter().eq(oracle.iter()));
+    }
+}
+
+#[quickcheck]
+fn find_byte(xs: Vec<u8>, skip: u8, byte: u8) {
+    let mut trial = PinnedByteDeque::with_capacity_per_chunk(5);
+    trial.extend_from_slice(&xs);
+    let skip = (skip as usize).min(xs.len());
+    trial.advance(skip);
+    let expected = xs[skip..].iter().position(|x| *x == byte);
+    assert_eq!(trial.find_byte(byte), expected);
+}
+
+#[test]
+fn buf_read() {
+    let mut trial = PinnedByteDeque::with_capacity_per_chunk(4);
+    trial.write_all(b"ab\ncdefg\nhi").unwrap();
+    assert_eq!(trial.fill_buf().unwrap(), b"ab\nc");
+    trial.consume(1);
+    assert_eq!(trial.fill_buf().unwrap(), b"b\nc");
+    let lines: Vec<_> = trial.lines().map(Result::unwrap).collect();
+    assert_eq!(lines, vec!["b", "cdefg", "hi"]);
+}
+
+#[test]
+fn read_from() {
+    let src: Vec<u8> = (0..100).collect();
+    let mut reader = &src[..];
+    let mut trial = PinnedByteDeque::with_capacity_per_chunk(16);
+    trial.push_back(200);
+    let mut reads = vec![];
+    loop {
+        let n = trial.read_from(&mut reader).unwrap();
+        if n == 0 {
+            break;
+        }
+        reads.push(n);
+    }
+    // Every read fills the rest of a chunk.
+    assert_eq!(reads, vec![15, 16, 16, 16, 16, 16, 5]);
+    assert_eq!(trial.pop_front(), Some(200));
+    assert!(trial.iter().eq(src.iter()));
+    assert_eq!(trial.stats().used_chunks, 7);
+}
+
+#[test]
+fn shared_chunks_stay_intact() {
+    let mut trial = PinnedByteDeque::with_capacity_per_chunk(4);
+    trial.write_all(b"abcdef").unwrap();
+    let snapshot = trial.snapshot();
+    trial.write_all(b"gh").unwrap();
+    trial.advance(5);
+    assert!(snapshot.iter().eq(b"abcdef".iter()));
+    assert!(trial.iter().eq(b"fgh".iter()));
+}
+
+#[test]
+fn as_io_slices() {
+    let mut trial = PinnedByteDeque::with_capacity_per_chunk(4);
+    trial.write_all(b"abcdefghij").unwrap();
+    let mut slices = [std::io::IoSlice::new(&[]); 2];
+    assert_eq!(trial.as_io_slices(&mut slices), 2);
+    assert_eq!(&*slices[1], b"efgh");
+    let mut slices = [std::io::IoSlice::new(&[]); 8];
+    let n = trial.as_io_slices(&mut slices);
+    assert_eq!(n, 3);
+    let mut out = vec![];
+    let written = out.write_vectored(&slices[..n]).unwrap();
+    assert_eq!(written, 10);
+    assert_eq!(out, b"abcdefghij");
+}
+
+#[quickcheck]
+fn bounded(writes: Vec<Vec<u8>>, max_len: u8, policy: u8) {
+    let policy = [
+        OverflowPolicy::Reject,
+        OverflowPolicy::EvictFront,
+        OverflowPolicy::EvictBack,
+    ][policy as usize % 3];
+    let max_len = max_len as usize % 20;
+    let mut oracle = PinnedDeque::<u8>::bounded_with_capacity_per_chunk(max_len, policy, 7);
+    let mut trial = PinnedByteDeque::from(PinnedDeque::bounded_with_capacity_per_chunk(
+        max_len, policy, 7,
+    ));
+    for w in writes.iter() {
+        // Bytes are pushed one by one into the oracle.
+        oracle.extend(w.iter().copied());
+        trial.extend_from_slice(w);
+        assert!(trial.iter().eq(oracle.iter()));
+    }
+}
+
+#[test]
+fn read_from_into_full_deque() {
+    let mut trial = PinnedByteDeque::from(PinnedDeque::bounded_with_capacity_per_chunk(
+        5,
+        OverflowPolicy::Reject,
+        4,
+    ));
+    let mut reader: &[u8] = b"abcdefg";
+    while trial.read_from(&mut reader).unwrap() > 0 {}
+    assert_eq!(trial.to_vec(), b"abcde");
+    assert_eq!(reader, b"fg");
+    assert_eq!(trial.write(b"h").unwrap(), 0);
+
+    let mut trial = PinnedByteDeque::from(PinnedDeque::bounded_with_capacity_per_chunk(
+        5,
+        OverflowPolicy::EvictFront,
+        4,
+    ));
+    let mut reader: &[u8] = b"abcdefg";
+    while trial.read_from(&mut reader).unwrap() > 0 {}
+    assert_eq!(trial.to_vec(), b"cdefg");
+}
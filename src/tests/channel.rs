@@ -0,0 +1,152 @@
+use crate::channel::*;
+use std::{
+    future::Future,
+    pin::pin,
+    sync::Arc,
+    task::{Context, Poll, Wake},
+    thread,
+};
+
+struct ThreadWaker(thread::Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+fn block_on<F: Future>(fut: F) -> F::Output {
+    let waker = Arc::new(ThreadWaker(thread::current())).into();
+    let mut cx = Context::from_waker(&waker);
+    let mut fut = pin!(fut);
+    loop {
+        match fut.as_mut().poll(&mut cx) {
+            Poll::Ready(res) => return res,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+#[test]
+fn unbounded_in_order() {
+    let (tx, rx) = unbounded();
+    let producers: Vec<_> = (0..4)
+        .map(|p| {
+            let tx = tx.clone();
+            thread::spawn(move || {
+                for i in 0..1000 {
+                    tx.send((p, i)).unwrap();
+                }
+            })
+        })
+        .collect();
+    drop(tx);
+    let mut last = [None; 4];
+    let mut cnt = 0;
+    while let Ok((p, i)) = rx.recv() {
+        assert!(last[p].map_or(true, |x| x < i));
+        last[p] = Some(i);
+        cnt += 1;
+    }
+    assert_eq!(cnt, 4000);
+    for p in producers {
+        p.join().unwrap();
+    }
+}
+
+#[test]
+fn bounded_blocks_senders() {
+    let (tx, rx) = bounded(3);
+    assert_eq!(tx.capacity(), Some(3));
+    for i in 0..3 {
+        tx.try_send(i).unwrap();
+    }
+    assert_eq!(tx.try_send(3), Err(TrySendError::Full(3)));
+    let producer = thread::spawn(move || {
+        for i in 3..100 {
+            tx.send(i).unwrap();
+        }
+    });
+    for i in 0..100 {
+        assert_eq!(rx.recv(), Ok(i));
+        assert!(rx.len() <= 3);
+    }
+    producer.join().unwrap();
+    assert_eq!(rx.recv(), Err(RecvError));
+}
+
+#[test]
+fn disconnection() {
+    let (tx, rx) = unbounded::<usize>();
+    assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+    tx.send(1).unwrap();
+    drop(tx);
+    assert_eq!(rx.try_recv(), Ok(1));
+    assert_eq!(rx.try_recv(), Err(TryRecvError::Disconnected));
+
+    let (tx, rx) = bounded::<usize>(1);
+    drop(rx);
+    assert_eq!(tx.send(2), Err(SendError(2)));
+    assert_eq!(tx.try_send(3), Err(TrySendError::Disconnected(3)));
+}
+
+#[test]
+fn recv_ref_in_place() {
+    let (tx, mut rx) = unbounded();
+    let producer = thread::spawn(move || {
+        for i in 0..1000usize {
+            tx.send([i; 64]).unwrap();
+        }
+    });
+    for i in 0..1000usize {
+        let msg = rx.recv_ref().unwrap();
+        let addr = &*msg as *const [usize; 64];
+        assert_eq!(*msg, [i; 64]);
+        thread::yield_now();
+        assert_eq!(&*msg as *const [usize; 64], addr);
+        assert_eq!(*msg, [i; 64]);
+    }
+    producer.join().unwrap();
+    assert!(rx.try_recv_ref().is_err());
+    assert!(rx.recv_ref().is_err());
+}
+
+#[test]
+fn futures() {
+    let (tx, rx) = bounded(2);
+    let producer = thread::spawn(move || {
+        block_on(async {
+            for i in 0..100 {
+                tx.send_async(i).await.unwrap();
+            }
+        })
+    });
+    let received = block_on(async {
+        let mut res = vec![];
+        while let Ok(i) = rx.recv_async().await {
+            res.push(i);
+        }
+        res
+    });
+    producer.join().unwrap();
+    assert_eq!(received, (0..100).collect::<Vec<_>>());
+}
+
+#[test]
+fn pending_send_keeps_one_waker() {
+    let (tx, rx) = bounded(1);
+    tx.send(0).unwrap();
+    let thread_waker = Arc::new(ThreadWaker(thread::current()));
+    let waker = thread_waker.clone().into();
+    let mut cx = Context::from_waker(&waker);
+    let mut fut = pin!(tx.send_async(1));
+    for _ in 0..10 {
+        assert!(fut.as_mut().poll(&mut cx).is_pending());
+    }
+    // Held by `thread_waker`, `waker` and the channel.
+    assert_eq!(Arc::strong_count(&thread_waker), 3);
+    assert_eq!(rx.recv(), Ok(0));
+    assert_eq!(Arc::strong_count(&thread_waker), 2);
+    assert!(fut.as_mut().poll(&mut cx).is_ready());
+    assert_eq!(rx.recv(), Ok(1));
+}
@@ -39,3 +39,36 @@ fn eq(a: Vec<usize>, b: Vec<usize>) {
     let trial_eq = trial_a.eq(&trial_b);
     assert_eq!(oracle_eq, trial_eq);
 }
+
+#[test]
+fn cmp_prefix() {
+    let a: PinnedDeque<usize> = [1].into();
+    let b: PinnedDeque<usize> = PinnedDeque::new();
+    assert_eq!(a.cmp(&b), std::cmp::Ordering::Greater);
+    assert_eq!(b.cmp(&a), std::cmp::Ordering::Less);
+}
+
+#[quickcheck]
+fn eq_slices(a: Vec<usize>, b: Vec<usize>) {
+    let trial = {
+        let mut res = PinnedDeque::<usize>::with_capacity_per_chunk(2);
+        res.push_back(0);
+        res.extend(a.iter().copied());
+        res.pop_front();
+        res
+    };
+    assert_eq!(trial == b, a == b);
+    assert_eq!(trial == b[..], a == b);
+    assert_eq!(trial.eq(&&b[..]), a == b);
+    assert_eq!(trial == b.iter().copied().collect::<VecDeque<_>>(), a == b);
+    assert!(trial == a);
+}
+
+#[test]
+fn eq_arrays() {
+    let trial: PinnedDeque<String> = ["a", "b", "c"].map(String::from).into();
+    assert!(trial == ["a", "b", "c"]);
+    assert!(trial.eq(&&["a", "b", "c"]));
+    assert!(trial != ["a", "b"]);
+    assert!(trial != ["a", "b", "d"]);
+}
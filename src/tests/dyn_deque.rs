@@ -0,0 +1,130 @@
+use crate::*;
+use quickcheck_macros::quickcheck;
+use std::{cell::Cell, collections::VecDeque, fmt::Debug, rc::Rc};
+
+#[derive(Debug, Clone)]
+enum DynOp {
+    PushBack(String),
+    PopBack,
+    PopFront,
+}
+
+impl quickcheck::Arbitrary for DynOp {
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        match u8::arbitrary(g) % 3 {
+            0 => Self::PushBack(String::arbitrary(g)),
+            1 => Self::PopBack,
+            2 => Self::PopFront,
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[quickcheck]
+fn strs(ops: Vec<DynOp>) {
+    let mut oracle: VecDeque<String> = VecDeque::new();
+    let mut trial = PinnedDynDeque::<str>::new();
+    for op in ops.into_iter() {
+        match op {
+            DynOp::PushBack(s) => {
+                assert_eq!(&*trial.push_back_str(&s), s.as_str());
+                oracle.push_back(s);
+            }
+            DynOp::PopBack => {
+                assert_eq!(trial.pop_back(), oracle.pop_back().is_some());
+            }
+            DynOp::PopFront => {
+                assert_eq!(trial.pop_front(), oracle.pop_front().is_some());
+            }
+        }
+        assert_eq!(trial.len(), oracle.len());
+        assert_eq!(trial.front(), oracle.front().map(String::as_str));
+        assert_eq!(trial.back(), oracle.back().map(String::as_str));
+        assert!(trial.iter().eq(oracle.iter().map(String::as_str)));
+        assert!(trial
+            .iter()
+            .rev()
+            .eq(oracle.iter().rev().map(String::as_str)));
+    }
+}
+
+#[quickcheck]
+fn iter_from_both_ends(xs: Vec<Vec<u16>>, n: usize) {
+    let mut trial = PinnedDynDeque::<[u16]>::with_chunk_size(1);
+    for x in xs.iter() {
+        trial.push_back_slice(x);
+    }
+    let n = if xs.is_empty() { 0 } else { n % xs.len() };
+    let mut it = trial.iter();
+    let mut res = vec![];
+    for _ in 0..n {
+        res.push(it.next().unwrap());
+    }
+    let mut back: Vec<_> = it.rev().collect();
+    back.reverse();
+    res.extend(back);
+    assert!(res.into_iter().eq(xs.iter().map(Vec::as_slice)));
+}
+
+#[test]
+fn records_are_pinned() {
+    let mut trial = PinnedDynDeque::<[u8]>::with_chunk_size(4096);
+    let mut addrs = vec![];
+    for i in 0..100usize {
+        let x = vec![i as u8; i * 10];
+        addrs.push(trial.push_back_slice(&x).as_ptr());
+    }
+    // A record larger than a chunk gets a chunk of its own.
+    addrs.push(trial.push_back_slice(&[7; 10000]).as_ptr());
+    assert!(trial.iter().map(<[u8]>::as_ptr).eq(addrs.iter().copied()));
+    assert_eq!(trial.back().unwrap().len(), 10000);
+    for i in 0..50 {
+        assert!(trial.pop_front());
+        assert_eq!(trial.front().unwrap().as_ptr(), addrs[i + 1]);
+    }
+}
+
+#[test]
+fn trait_objects() {
+    let dropped = Rc::new(Cell::new(0));
+    struct Counted(u64, Rc<Cell<usize>>);
+    impl Debug for Counted {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}", self.0)
+        }
+    }
+    impl Drop for Counted {
+        fn drop(&mut self) {
+            self.1.set(self.1.get() + 1);
+        }
+    }
+    let mut trial = PinnedDynDeque::<dyn Debug>::new();
+    trial.push_back_unsize(1u8, |x| x);
+    trial.push_back_unsize("two", |x| x);
+    trial.push_back_unsize([3u64; 4], |x| x);
+    trial.push_back_unsize(Counted(4, dropped.clone()), |x| x);
+    trial.push_back_unsize(Counted(5, dropped.clone()), |x| x);
+    assert_eq!(format!("{:?}", trial), r#"[1, "two", [3, 3, 3, 3], 4, 5]"#);
+    assert!(trial.pop_back());
+    assert_eq!(dropped.get(), 1);
+    drop(trial);
+    assert_eq!(dropped.get(), 2);
+}
+
+#[test]
+fn push_back_unsize_checks_coerce() {
+    let mut trial = PinnedDynDeque::<[u8]>::new();
+    let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        trial.push_back_unsize([1u8; 4], |x| &mut x[..2]);
+    }));
+    assert!(res.is_err());
+    assert!(trial.is_empty());
+    // The chunk fetched for the value is recycled as well when `coerce` panics.
+    let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        trial.push_back_unsize([2u8; 4], |_| panic!("no coercion"));
+    }));
+    assert!(res.is_err());
+    assert!(trial.is_empty());
+    trial.push_back_unsize([3u8; 4], |x| x);
+    assert_eq!(format!("{:?}", trial), "[[3, 3, 3, 3]]");
+}
@@ -0,0 +1,177 @@
+use super::*;
+use crate::*;
+use quickcheck_macros::quickcheck;
+use std::collections::VecDeque;
+
+#[quickcheck]
+fn push_with(ops: Vec<Op>) {
+    let mut oracle = VecDeque::new();
+    let mut trial = PinnedDeque::<usize>::with_capacity_per_chunk(3);
+    for op in ops.into_iter() {
+        match op {
+            Op::PopBack => {
+                assert_eq!(trial.pop_back(), oracle.pop_back());
+            }
+            Op::PopFront => {
+                assert_eq!(trial.pop_front(), oracle.pop_front());
+            }
+            Op::PushBack(n) => {
+                oracle.push_back(n);
+                trial.push_back_with(|| n);
+            }
+            Op::PushFront(n) => {
+                oracle.push_front(n);
+                trial.push_front_with(|| n);
+            }
+        }
+        assert_eq!(trial.len(), oracle.len());
+        assert!(trial.iter().eq(oracle.iter()));
+    }
+}
+
+#[test]
+fn uninit_slot_is_final() {
+    let mut trial = PinnedDeque::<[u64; 512]>::with_capacity_per_chunk(2);
+    for i in 0..5 {
+        let slot = trial.push_back_uninit();
+        let addr = slot.as_ptr();
+        unsafe {
+            let elem = &mut *slot.as_mut_ptr();
+            elem.fill(i);
+            trial.commit_back_uninit();
+        }
+        assert_eq!(trial.back().unwrap() as *const _, addr);
+        let slot = trial.push_front_uninit();
+        let addr = slot.as_ptr();
+        slot.write([i + 100; 512]);
+        unsafe { trial.commit_front_uninit() };
+        assert_eq!(trial.front().unwrap() as *const _, addr);
+    }
+    let fronts: Vec<_> = trial.iter().map(|x| x[511]).collect();
+    assert_eq!(fronts, vec![104, 103, 102, 101, 100, 0, 1, 2, 3, 4]);
+    assert_eq!(trial.first_seq(), 5u64.wrapping_neg());
+}
+
+#[test]
+fn uncommitted_slot_is_no_element() {
+    let mut trial = PinnedDeque::<String>::with_capacity_per_chunk(2);
+    trial.push_back("a".to_string());
+    trial.push_back_uninit();
+    trial.push_front_uninit();
+    trial.push_back("b".to_string());
+    assert_eq!(trial.len(), 2);
+    assert!(trial.iter().eq(["a", "b"]));
+}
+
+#[test]
+fn push_with_bounded() {
+    let mut trial =
+        PinnedDeque::<usize>::bounded_with_capacity_per_chunk(3, OverflowPolicy::EvictFront, 2);
+    for i in 0..10 {
+        trial.push_back_with(|| i);
+    }
+    assert!(trial.iter().eq(&[7, 8, 9]));
+
+    let mut trial =
+        PinnedDeque::<String>::bounded_with_capacity_per_chunk(2, OverflowPolicy::Reject, 2);
+    for i in 0..5 {
+        trial.push_front_with(|| i.to_string());
+    }
+    assert!(trial.iter().eq(["1", "0"]));
+}
+
+#[test]
+fn reserve_slot() {
+    let mut trial = PinnedDeque::<String>::with_capacity_per_chunk(2);
+    let mut addrs = vec![];
+    for i in 0..3 {
+        let mut slot = trial.reserve_back_slot().unwrap();
+        let addr = slot.as_ptr();
+        slot.as_uninit_mut().write(format!("b{}", i));
+        let elem = unsafe { slot.assume_init() };
+        assert_eq!(elem as *const String, addr);
+        addrs.push(addr);
+        let slot = trial.reserve_front_slot().unwrap();
+        let addr = slot.as_ptr();
+        assert_eq!(slot.write(format!("f{}", i)) as *const String, addr);
+        addrs.insert(0, addr);
+    }
+    assert!(trial.iter().eq(["f2", "f1", "f0", "b0", "b1", "b2"]));
+    assert!(trial.iter().map(|x| x as *const String).eq(addrs));
+}
+
+#[test]
+fn drop_slot_without_commit() {
+    let mut trial = PinnedDeque::<String>::with_capacity_per_chunk(2);
+    trial.push_back("a".to_string());
+    let addr = trial.reserve_back_slot().unwrap().as_ptr();
+    trial.reserve_front_slot().unwrap();
+    assert_eq!(trial.len(), 1);
+    assert!(trial.iter().eq(["a"]));
+    trial.push_back("b".to_string());
+    assert_eq!(trial.back().unwrap() as *const String, addr);
+}
+
+#[test]
+fn reserve_slot_bounded() {
+    let mut trial =
+        PinnedDeque::<usize>::bounded_with_capacity_per_chunk(2, OverflowPolicy::Reject, 2);
+    trial.reserve_back_slot().unwrap().write(1);
+    trial.reserve_front_slot().unwrap().write(0);
+    assert!(trial.reserve_back_slot().is_none());
+    assert!(trial.reserve_front_slot().is_none());
+
+    let mut trial =
+        PinnedDeque::<usize>::bounded_with_capacity_per_chunk(2, OverflowPolicy::EvictBack, 2);
+    for i in 0..5 {
+        trial.reserve_front_slot().unwrap().write(i);
+    }
+    assert!(trial.iter().eq(&[4, 3]));
+}
+
+#[test]
+fn drop_slot_on_full_deque() {
+    for policy in [OverflowPolicy::EvictFront, OverflowPolicy::EvictBack] {
+        let mut trial = PinnedDeque::<String>::bounded_with_capacity_per_chunk(2, policy, 2);
+        trial.extend(["1", "2"].map(String::from));
+        trial.reserve_back_slot().unwrap();
+        assert_eq!(trial, ["1", "2"]);
+        trial.reserve_front_slot().unwrap();
+        assert_eq!(trial, ["1", "2"]);
+    }
+    let mut trial =
+        PinnedDeque::<String>::bounded_with_capacity_per_chunk(2, OverflowPolicy::EvictFront, 2);
+    trial.extend(["1", "2"].map(String::from));
+    trial.reserve_back_slot().unwrap().write("3".to_owned());
+    assert_eq!(trial, ["2", "3"]);
+}
+
+#[test]
+fn commit_into_full_deque_gives_elem_back() {
+    let mut trial =
+        PinnedDeque::<String>::bounded_with_capacity_per_chunk(1, OverflowPolicy::Reject, 2);
+    trial.push_back("a".to_owned());
+    trial.push_back_uninit().write("b".to_owned());
+    assert_eq!(unsafe { trial.commit_back_uninit() }.as_deref(), Some("b"));
+    trial.push_front_uninit().write("c".to_owned());
+    assert_eq!(unsafe { trial.commit_front_uninit() }.as_deref(), Some("c"));
+    assert_eq!(trial, ["a"]);
+}
+
+#[test]
+fn push_with_panicking_keeps_evicted_elem() {
+    for policy in [OverflowPolicy::EvictFront, OverflowPolicy::EvictBack] {
+        let mut trial = PinnedDeque::<String>::bounded_with_capacity_per_chunk(2, policy, 2);
+        trial.extend(["1", "2"].map(String::from));
+        let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            trial.push_back_with(|| panic!("no element"));
+        }));
+        assert!(res.is_err());
+        assert_eq!(trial, ["1", "2"]);
+        let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            trial.push_front_with(|| panic!("no element"));
+        }));
+        assert!(res.is_err());
+        assert_eq!(trial, ["1", "2"]);
+    }
+}
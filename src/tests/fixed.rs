@@ -0,0 +1,85 @@
+use super::*;
+use crate::*;
+use quickcheck_macros::quickcheck;
+use std::collections::VecDeque;
+
+#[quickcheck]
+fn basic_ops(ops: Vec<Op>) {
+    let mut oracle = VecDeque::new();
+    let mut trial = FixedChunkDeque::<usize, 2>::new();
+    for op in ops.into_iter() {
+        match op {
+            Op::PopBack => {
+                assert_eq!(trial.back(), oracle.back());
+                assert_eq!(trial.pop_back(), oracle.pop_back());
+            }
+            Op::PopFront => {
+                assert_eq!(trial.front(), oracle.front());
+                assert_eq!(trial.pop_front(), oracle.pop_front());
+            }
+            Op::PushBack(n) => {
+                oracle.push_back(n);
+                trial.push_back(n);
+            }
+            Op::PushFront(n) => {
+                oracle.push_front(n);
+                trial.push_front(n);
+            }
+        }
+    }
+    assert_eq!(trial.len(), oracle.len());
+    {
+        let trial: VecDeque<_> = trial.iter().copied().collect();
+        assert_eq!(trial, oracle);
+    }
+    {
+        let trial: VecDeque<_> = trial.iter().rev().copied().collect();
+        let oracle: VecDeque<_> = oracle.iter().rev().copied().collect();
+        assert_eq!(trial, oracle);
+    }
+    {
+        let trial: VecDeque<_> = trial.iter_mut().map(|x| *x).collect();
+        assert_eq!(trial, oracle);
+    }
+    {
+        let trial: VecDeque<_> = (0..trial.len())
+            .map(|idx| *trial.get(idx).unwrap())
+            .collect();
+        assert_eq!(trial, oracle);
+    }
+    {
+        let trial: VecDeque<_> = (0..trial.len())
+            .map(|idx| *trial.get_mut(idx).unwrap())
+            .collect();
+        assert_eq!(trial, oracle);
+    }
+    assert_eq!(trial.get(trial.len()), None);
+    let trial: VecDeque<_> = trial.into_iter().collect();
+    assert_eq!(trial, oracle);
+}
+
+#[test]
+fn drop_elems() {
+    use std::rc::Rc;
+    let counter = Rc::new(());
+    {
+        let mut trial = FixedChunkDeque::<Rc<()>, 2>::new();
+        for _ in 0..5 {
+            trial.push_back(counter.clone());
+        }
+        trial.pop_front();
+        assert_eq!(Rc::strong_count(&counter), 5);
+        trial.clear();
+        assert_eq!(Rc::strong_count(&counter), 1);
+        assert!(trial.is_empty());
+        trial.push_front(counter.clone());
+    }
+    assert_eq!(Rc::strong_count(&counter), 1);
+}
+
+#[test]
+fn iter_mut() {
+    let mut trial: FixedChunkDeque<usize, 2> = (0..5).collect();
+    trial.iter_mut().for_each(|x| *x *= 2);
+    assert_eq!(format!("{trial:?}"), "[0, 2, 4, 6, 8]");
+}
@@ -74,3 +74,52 @@ fn fromiter() {
     let trial: PinnedDeque<_> = oracle.clone().into_iter().collect();
     assert_eq!(format!("{trial:?}"), format!("{oracle:?}"));
 }
+
+#[quickcheck]
+fn convert(a: Vec<usize>, n: usize) {
+    let oracle: VecDeque<_> = a.iter().copied().collect();
+    let trial = PinnedDeque::from(a.clone());
+    assert!(trial.iter().eq(a.iter()));
+    assert_eq!(trial.to_vec(), a);
+    assert_eq!(Vec::from(trial), a);
+
+    let mut rotated = oracle.clone();
+    rotated.rotate_left(if a.is_empty() { 0 } else { n % a.len() });
+    let trial = PinnedDeque::from(rotated.clone());
+    assert!(trial.iter().eq(rotated.iter()));
+    assert_eq!(VecDeque::from(trial), rotated);
+
+    let mut trial = PinnedDeque::<usize>::with_capacity_per_chunk(3);
+    trial.extend(a.iter().copied());
+    trial.push_front(n);
+    trial.pop_front();
+    assert_eq!(&*trial.into_boxed_slice(), &a[..]);
+}
+
+#[test]
+fn convert_moves_elements() {
+    let rc = std::rc::Rc::new(());
+    let v: Vec<_> = (0..2000).map(|_| rc.clone()).collect();
+    let mut trial = PinnedDeque::from(v);
+    assert_eq!(std::rc::Rc::strong_count(&rc), 2001);
+    trial.pop_front();
+    let snapshot = trial.snapshot();
+    let v = Vec::from(trial);
+    assert_eq!(v.len(), 1999);
+    assert_eq!(std::rc::Rc::strong_count(&rc), 1999 * 2 + 1);
+    drop(snapshot);
+    drop(v);
+    assert_eq!(std::rc::Rc::strong_count(&rc), 1);
+}
+
+#[quickcheck]
+fn hash(a: Vec<usize>) {
+    use std::{collections::hash_map::RandomState, hash::BuildHasher};
+    let oracle: VecDeque<_> = a.iter().copied().collect();
+    let mut trial = PinnedDeque::<usize>::with_capacity_per_chunk(2);
+    trial.push_back(0);
+    trial.extend(a.iter().copied());
+    trial.pop_front();
+    let state = RandomState::new();
+    assert_eq!(state.hash_one(&trial), state.hash_one(&oracle));
+}
@@ -1,3 +1,5 @@
+#![allow(clippy::while_let_loop)]
+
 use super::*;
 use crate::*;
 use quickcheck_macros::*;
@@ -0,0 +1,116 @@
+use crate::lru::*;
+use quickcheck::Arbitrary;
+use quickcheck_macros::quickcheck;
+
+#[derive(Debug, Clone)]
+enum LruOp {
+    Insert(u8, usize),
+    Get(u8),
+    Remove(u8),
+    PopLru,
+}
+
+impl Arbitrary for LruOp {
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        let key = u8::arbitrary(g) % 8;
+        match u8::arbitrary(g) % 4 {
+            0 => Self::Insert(key, usize::arbitrary(g)),
+            1 => Self::Get(key),
+            2 => Self::Remove(key),
+            3 => Self::PopLru,
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[quickcheck]
+fn lru(ops: Vec<LruOp>, cap: u8) {
+    let cap = (cap % 5) as usize + 1;
+    // From the least recently used to the most.
+    let mut oracle: Vec<(u8, usize)> = vec![];
+    let mut trial = LruCache::new(cap);
+    for op in ops.into_iter() {
+        match op {
+            LruOp::Insert(k, v) => {
+                let expected = if let Some(pos) = oracle.iter().position(|(x, _)| *x == k) {
+                    let (_, old) = oracle.remove(pos);
+                    Insertion::Replaced(old)
+                } else if oracle.len() == cap {
+                    let (k, v) = oracle.remove(0);
+                    Insertion::Evicted(k, v)
+                } else {
+                    Insertion::Inserted
+                };
+                oracle.push((k, v));
+                assert_eq!(trial.push(k, v), expected);
+            }
+            LruOp::Get(k) => {
+                let expected = oracle.iter().position(|(x, _)| *x == k).map(|pos| {
+                    let entry = oracle.remove(pos);
+                    oracle.push(entry);
+                    entry.1
+                });
+                assert_eq!(trial.get(&k).copied(), expected);
+            }
+            LruOp::Remove(k) => {
+                let expected = oracle
+                    .iter()
+                    .position(|(x, _)| *x == k)
+                    .map(|pos| oracle.remove(pos).1);
+                assert_eq!(trial.remove(&k), expected);
+            }
+            LruOp::PopLru => {
+                let expected = if oracle.is_empty() {
+                    None
+                } else {
+                    Some(oracle.remove(0))
+                };
+                assert_eq!(trial.pop_lru(), expected);
+            }
+        }
+        assert_eq!(trial.len(), oracle.len());
+        let entries: Vec<_> = trial.iter().map(|(k, v)| (*k, *v)).rev().collect();
+        assert_eq!(entries, oracle);
+    }
+}
+
+#[test]
+fn values_never_move() {
+    let mut trial = LruCache::new(2);
+    trial.insert("a".to_owned(), 0);
+    let ptr: *const i32 = trial.peek("a").unwrap();
+    for i in 0..100 {
+        trial.insert(format!("b{i}"), i);
+        assert_eq!(trial.get("a"), Some(&0));
+    }
+    assert_eq!(trial.peek("a").unwrap() as *const i32, ptr);
+    assert_eq!(trial.peek_lru(), Some((&"b99".to_owned(), &99)));
+}
+
+#[test]
+fn insert() {
+    let mut trial = LruCache::new(2);
+    assert_eq!(trial.insert("a", 0), None);
+    assert_eq!(trial.insert("b", 1), None);
+    assert_eq!(trial.insert("a", 2), Some(0));
+    // "b" is evicted, which is not mistaken for a replaced value.
+    assert_eq!(trial.insert("c", 3), None);
+    assert!(!trial.contains_key("b"));
+    assert_eq!(trial.push("a", 4), Insertion::Replaced(2));
+    assert_eq!(trial.push("d", 5), Insertion::Evicted("c", 3));
+}
+
+#[test]
+fn send_and_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<LruCache<String, usize>>();
+    let mut trial = LruCache::new(2);
+    trial.insert("a".to_owned(), 0);
+    let trial = std::thread::spawn(move || {
+        trial.insert("b".to_owned(), 1);
+        trial
+    })
+    .join()
+    .unwrap();
+    assert_eq!(trial.peek("b"), Some(&1));
+}
@@ -1,6 +1,29 @@
+mod alloc;
 mod basic_ops;
+mod bounded;
+#[cfg(feature = "bytes")]
+mod buf;
+#[cfg(feature = "std")]
+mod byte_deque;
+#[cfg(feature = "std")]
+mod channel;
 mod cmp;
+mod dyn_deque;
+mod emplace;
+mod fixed;
 mod intf;
 mod iter;
+#[cfg(feature = "std")]
+mod lru;
+mod owned_chunk;
+mod peek;
+#[cfg(feature = "pin-init")]
+mod pin_init;
+mod seq;
+mod snapshot;
+mod splice;
+mod stats;
 mod util;
+mod view;
+mod windows;
 use self::util::*;
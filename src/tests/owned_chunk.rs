@@ -0,0 +1,136 @@
+use crate::*;
+use std::{rc::Rc, thread};
+
+#[test]
+fn hand_over_chunks_between_threads() {
+    let mut producer = PinnedDeque::<usize>::with_capacity_per_chunk(4);
+    producer.extend(0..10);
+    let addrs: Vec<_> = producer
+        .iter()
+        .map(|x| x as *const usize as usize)
+        .collect();
+    let mut chunks = vec![];
+    while let Some(chunk) = producer.pop_front_chunk() {
+        chunks.push(chunk);
+    }
+    assert!(producer.is_empty());
+    assert_eq!(producer.first_seq(), 10);
+    let consumed = thread::spawn(move || {
+        let mut consumer = PinnedDeque::<usize>::with_capacity_per_chunk(4);
+        for chunk in chunks {
+            consumer.push_back_chunk(chunk);
+        }
+        assert_eq!(consumer.stats().used_chunks, 3);
+        consumer
+            .iter()
+            .map(|x| (*x, x as *const usize as usize))
+            .collect::<Vec<_>>()
+    })
+    .join()
+    .unwrap();
+    assert!(consumed.iter().map(|x| x.0).eq(0..10));
+    assert!(consumed.iter().map(|x| x.1).eq(addrs));
+}
+
+#[test]
+fn owned_chunk_as_slice() {
+    let mut trial = PinnedDeque::<i32>::with_capacity_per_chunk(4);
+    trial.extend(0..6);
+    trial.push_front(-1);
+    let mut chunk = trial.pop_front_chunk().unwrap();
+    assert_eq!(&*chunk, &[-1]);
+    assert_eq!(chunk.capacity(), 4);
+    let mut chunk2 = trial.pop_front_chunk().unwrap();
+    chunk2[0] = 100;
+    assert_eq!(&*chunk2, &[100, 1, 2, 3]);
+    assert_eq!(format!("{:?}", chunk2), "[100, 1, 2, 3]");
+    assert!(chunk2.into_iter().rev().eq([3, 2, 1, 100]));
+    for x in &mut chunk {
+        *x += 1;
+    }
+    assert_eq!(chunk.into_vec(), vec![0]);
+    assert!(trial.iter().eq(&[4, 5]));
+    assert_eq!(trial.first_seq(), 4);
+}
+
+#[test]
+fn owned_chunk_drops_elements() {
+    let rc = Rc::new(());
+    let mut trial = PinnedDeque::with_capacity_per_chunk(4);
+    trial.extend((0..8).map(|_| rc.clone()));
+    drop(trial.pop_front_chunk());
+    assert_eq!(Rc::strong_count(&rc), 5);
+    let mut iter = trial.pop_front_chunk().unwrap().into_iter();
+    iter.next();
+    assert_eq!(iter.len(), 3);
+    drop(iter);
+    assert_eq!(Rc::strong_count(&rc), 1);
+    trial.extend((0..3).map(|_| rc.clone()));
+    let v = trial.pop_front_chunk().unwrap().into_vec();
+    assert_eq!(Rc::strong_count(&rc), 4);
+    drop(v);
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
+
+#[test]
+fn push_back_chunk_moves_unaligned_elements() {
+    let mut src = PinnedDeque::<i32>::with_capacity_per_chunk(4);
+    src.extend(0..6);
+    let mut trial = PinnedDeque::<i32>::with_capacity_per_chunk(4);
+    trial.push_back(-1);
+    let chunk = src.pop_front_chunk().unwrap();
+    trial.push_back_chunk(chunk);
+    assert!(trial.iter().eq(&[-1, 0, 1, 2, 3]));
+    assert_eq!(trial.stats().used_chunks, 2);
+
+    let mut other = PinnedDeque::<i32>::with_capacity_per_chunk(3);
+    other.push_back_chunk(src.pop_front_chunk().unwrap());
+    assert!(other.iter().eq(&[4, 5]));
+
+    let mut bounded =
+        PinnedDeque::<i32>::bounded_with_capacity_per_chunk(3, OverflowPolicy::EvictFront, 4);
+    src.extend(0..4);
+    bounded.push_back_chunk(src.pop_front_chunk().unwrap());
+    assert!(bounded.iter().eq(&[1, 2, 3]));
+}
+
+#[test]
+fn push_back_chunk_into_bounded() {
+    let mut src = PinnedDeque::<i32>::with_capacity_per_chunk(4);
+    src.extend(0..10);
+    let mut trial =
+        PinnedDeque::<i32>::bounded_with_capacity_per_chunk(6, OverflowPolicy::Reject, 4);
+    let chunks = trial.stats().free_chunks;
+    assert!(trial
+        .push_back_chunk(src.pop_front_chunk().unwrap())
+        .is_none());
+    assert!(trial.iter().eq(&[0, 1, 2, 3]));
+    // The chunk does not fit, and it is rejected as a whole.
+    let rejected = trial
+        .push_back_chunk(src.pop_front_chunk().unwrap())
+        .unwrap();
+    assert_eq!(*rejected, [4, 5, 6, 7]);
+    assert!(trial.iter().eq(&[0, 1, 2, 3]));
+    assert_eq!(trial.len(), 4);
+    let stats = trial.stats();
+    assert_eq!(stats.used_chunks + stats.free_chunks, chunks);
+
+    let mut trial =
+        PinnedDeque::<i32>::bounded_with_capacity_per_chunk(6, OverflowPolicy::EvictFront, 4);
+    trial.extend(0..4);
+    assert!(trial.push_back_chunk(rejected).is_none());
+    assert!(trial.iter().eq(&[2, 3, 4, 5, 6, 7]));
+    assert_eq!(trial.max_len(), Some(6));
+}
+
+#[test]
+fn pop_front_chunk_with_snapshot() {
+    let mut trial = PinnedDeque::<i32>::with_capacity_per_chunk(4);
+    trial.extend(0..6);
+    let snapshot = trial.snapshot();
+    let mut chunk = trial.pop_front_chunk().unwrap();
+    chunk[0] = 100;
+    assert!(snapshot.iter().eq((0..6).collect::<Vec<_>>().iter()));
+    assert_eq!(&*chunk, &[100, 1, 2, 3]);
+    assert!(trial.iter().eq(&[4, 5]));
+}
@@ -0,0 +1,94 @@
+use crate::*;
+use quickcheck_macros::quickcheck;
+use std::{cell::Cell, collections::VecDeque, rc::Rc};
+
+#[quickcheck]
+fn pop_if(xs: Vec<u8>, pivot: u8) {
+    let mut oracle: VecDeque<u8> = xs.iter().copied().collect();
+    let mut trial = PinnedDeque::<u8>::with_capacity_per_chunk(3);
+    trial.extend(xs.iter().copied());
+    loop {
+        let expected = if oracle.front().is_some_and(|x| *x < pivot) {
+            oracle.pop_front()
+        } else {
+            None
+        };
+        assert_eq!(trial.pop_front_if(|x| *x < pivot), expected);
+        let expected = if oracle.back().is_some_and(|x| *x >= pivot) {
+            oracle.pop_back()
+        } else {
+            None
+        };
+        assert_eq!(trial.pop_back_if(|x| *x >= pivot), expected);
+        if oracle.is_empty() {
+            break;
+        }
+        oracle.pop_front();
+        trial.pop_front();
+    }
+    assert!(trial.is_empty());
+}
+
+#[test]
+fn front_peek_mut() {
+    let mut trial = PinnedDeque::<usize>::with_capacity_per_chunk(3);
+    assert!(trial.front_peek_mut().is_none());
+    trial.extend(0..5);
+    let mut peek = trial.front_peek_mut().unwrap();
+    assert_eq!(*peek, 0);
+    *peek = 10;
+    assert_eq!(PeekMut::pop(peek), 10);
+    *trial.front_peek_mut().unwrap() += 10;
+    assert_eq!(trial.iter().copied().collect::<Vec<_>>(), vec![11, 2, 3, 4]);
+    assert_eq!(trial.first_seq(), 1);
+}
+
+#[quickcheck]
+fn pop_front_while(xs: Vec<u8>, pivot: u8) {
+    let mut oracle: VecDeque<u8> = xs.iter().copied().collect();
+    let mut trial = PinnedDeque::<u8>::with_capacity_per_chunk(3);
+    trial.extend(xs.iter().copied());
+    let mut cnt = 0;
+    while oracle.front().is_some_and(|x| *x < pivot) {
+        oracle.pop_front();
+        cnt += 1;
+    }
+    assert_eq!(trial.pop_front_while(|x| *x < pivot), cnt);
+    assert_eq!(trial.first_seq(), cnt as u64);
+    assert_eq!(trial.len(), oracle.len());
+    assert!(trial.iter().eq(oracle.iter()));
+}
+
+#[test]
+fn pop_front_while_drops() {
+    let dropped = Rc::new(Cell::new(0));
+    struct Counted(usize, Rc<Cell<usize>>);
+    impl Drop for Counted {
+        fn drop(&mut self) {
+            self.1.set(self.1.get() + 1);
+        }
+    }
+    let mut trial = PinnedDeque::with_capacity_per_chunk(4);
+    for i in 0..10 {
+        trial.push_back(Counted(i, dropped.clone()));
+    }
+    assert_eq!(trial.pop_front_while(|x| x.0 < 7), 7);
+    assert_eq!(dropped.get(), 7);
+    assert_eq!(trial.stats().used_chunks, 2);
+    drop(trial);
+    assert_eq!(dropped.get(), 10);
+}
+
+#[quickcheck]
+fn drain_front_while(xs: Vec<u8>, pivot: u8) {
+    let mut oracle: VecDeque<u8> = xs.iter().copied().collect();
+    let mut trial = PinnedDeque::<u8>::with_capacity_per_chunk(3);
+    trial.extend(xs.iter().copied());
+    let mut expected = vec![];
+    while oracle.front().is_some_and(|x| *x < pivot) {
+        expected.push(oracle.pop_front().unwrap());
+    }
+    let drained: Vec<_> = trial.drain_front_while(|x| *x < pivot).collect();
+    assert_eq!(drained, expected);
+    assert!(trial.iter().eq(oracle.iter()));
+}
@@ -0,0 +1,62 @@
+use crate::*;
+use core::{marker::PhantomPinned, pin::Pin, ptr};
+use pinned_init::{pin_init_from_closure, PinInit};
+
+/// Points to itself.
+struct SelfRef {
+    id: usize,
+    this: *const SelfRef,
+    _pin: PhantomPinned,
+}
+
+impl SelfRef {
+    fn new(id: usize) -> impl PinInit<Self, usize> {
+        unsafe {
+            pin_init_from_closure(move |slot: *mut Self| {
+                if id % 3 == 2 {
+                    return Err(id);
+                }
+                slot.write(SelfRef {
+                    id,
+                    this: slot,
+                    _pin: PhantomPinned,
+                });
+                Ok(())
+            })
+        }
+    }
+
+    fn check(&self) {
+        assert!(ptr::eq(self, self.this));
+    }
+}
+
+#[test]
+fn push_pin_init() {
+    let mut trial = PinnedDeque::<SelfRef>::with_capacity_per_chunk(2);
+    let mut expected = vec![];
+    for i in 0..10 {
+        let res = unsafe { trial.push_back_pin_init(SelfRef::new(i)) };
+        match res {
+            Ok(elem) => {
+                let elem: Pin<&mut SelfRef> = elem;
+                elem.check();
+                expected.push(i);
+            }
+            Err(e) => assert_eq!(e, i),
+        }
+        let res = unsafe { trial.push_front_pin_init(SelfRef::new(i + 100)) };
+        match res {
+            Ok(elem) => {
+                elem.check();
+                expected.insert(0, i + 100);
+            }
+            Err(e) => assert_eq!(e, i + 100),
+        }
+    }
+    assert!(trial.iter().map(|x| x.id).eq(expected));
+    trial.iter().for_each(SelfRef::check);
+    let n = trial.pop_front_while(|x| x.id >= 100);
+    assert_eq!(n, 7);
+    trial.iter().for_each(SelfRef::check);
+}
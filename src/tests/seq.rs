@@ -0,0 +1,79 @@
+use super::*;
+use crate::*;
+use quickcheck_macros::quickcheck;
+use std::collections::VecDeque;
+
+#[quickcheck]
+fn seq(ops: Vec<Op>) {
+    // Pairs of sequence numbers and elements.
+    let mut oracle: VecDeque<(u64, usize)> = VecDeque::new();
+    let mut next_front_seq = 0u64;
+    let mut trial = PinnedDeque::<usize>::with_capacity_per_chunk(3);
+    for op in ops.into_iter() {
+        match op {
+            Op::PopBack => {
+                let _ = oracle.pop_back();
+                let _ = trial.pop_back();
+            }
+            Op::PopFront => {
+                if oracle.pop_front().is_some() {
+                    next_front_seq = next_front_seq.wrapping_add(1);
+                }
+                let _ = trial.pop_front();
+            }
+            Op::PushBack(n) => {
+                let seq = next_front_seq.wrapping_add(oracle.len() as u64);
+                oracle.push_back((seq, n));
+                assert_eq!(trial.push_back_seq(n), seq);
+            }
+            Op::PushFront(n) => {
+                next_front_seq = next_front_seq.wrapping_sub(1);
+                oracle.push_front((next_front_seq, n));
+                trial.push_front(n);
+            }
+        }
+        assert_eq!(trial.first_seq(), next_front_seq);
+        assert_eq!(
+            trial.end_seq(),
+            next_front_seq.wrapping_add(oracle.len() as u64)
+        );
+        for (seq, x) in oracle.iter() {
+            assert_eq!(trial.get_seq(*seq), Some(x));
+        }
+        assert_eq!(trial.get_seq(trial.end_seq()), None);
+        assert_eq!(trial.get_seq(trial.first_seq().wrapping_sub(1)), None);
+    }
+    for (i, (seq, _)) in oracle.iter().enumerate() {
+        let it = trial.iter_from_seq(*seq).unwrap();
+        assert_eq!(it.len(), oracle.len() - i);
+        let trial: Vec<_> = it.copied().collect();
+        let oracle: Vec<_> = oracle.iter().skip(i).map(|(_, x)| *x).collect();
+        assert_eq!(trial, oracle);
+    }
+    assert_eq!(trial.iter_from_seq(trial.end_seq()).unwrap().next(), None);
+    assert!(trial
+        .iter_from_seq(trial.end_seq().wrapping_add(1))
+        .is_none());
+}
+
+#[test]
+fn iter_from_seq_backwards() {
+    let mut trial = PinnedDeque::<usize>::with_capacity_per_chunk(2);
+    for x in 0..6 {
+        trial.push_back(x);
+    }
+    trial.pop_front();
+    let seq = trial.first_seq() + 2;
+    let it = trial.iter_from_seq(seq).unwrap();
+    assert_eq!(it.rev().copied().collect::<Vec<_>>(), vec![5, 4, 3]);
+}
+
+#[test]
+fn clear() {
+    let mut trial = PinnedDeque::<usize>::new();
+    trial.push_back(0);
+    trial.push_back(1);
+    trial.clear();
+    assert_eq!(trial.first_seq(), 2);
+    assert_eq!(trial.push_back_seq(2), 2);
+}
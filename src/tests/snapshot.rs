@@ -0,0 +1,146 @@
+use super::*;
+use crate::*;
+use quickcheck_macros::quickcheck;
+use std::{collections::VecDeque, rc::Rc};
+
+#[quickcheck]
+fn snapshot(ops: Vec<(Op, bool)>) {
+    let counter = Rc::new(());
+    {
+        let mut oracle = VecDeque::new();
+        let mut trial = PinnedDeque::<(usize, Rc<()>)>::with_capacity_per_chunk(3);
+        let mut snapshots = vec![];
+        for (op, take_snapshot) in ops.into_iter() {
+            match op {
+                Op::PopBack => {
+                    assert_eq!(trial.pop_back().map(|x| x.0), oracle.pop_back());
+                }
+                Op::PopFront => {
+                    assert_eq!(trial.pop_front().map(|x| x.0), oracle.pop_front());
+                }
+                Op::PushBack(n) => {
+                    oracle.push_back(n);
+                    trial.push_back((n, counter.clone()));
+                }
+                Op::PushFront(n) => {
+                    oracle.push_front(n);
+                    trial.push_front((n, counter.clone()));
+                }
+            }
+            if let (Some(x), Some(y)) = (oracle.get_mut(1), trial.get_mut(1)) {
+                *x = x.wrapping_add(1);
+                y.0 = y.0.wrapping_add(1);
+            }
+            if take_snapshot {
+                snapshots.push((oracle.clone(), trial.snapshot()));
+            }
+            if snapshots.len() > 3 {
+                snapshots.remove(0);
+            }
+            let trial: VecDeque<_> = trial.iter().map(|x| x.0).collect();
+            assert_eq!(trial, oracle);
+        }
+        for (oracle, snapshot) in snapshots.iter() {
+            assert_eq!(snapshot.len(), oracle.len());
+            let trial: VecDeque<_> = snapshot.iter().map(|x| x.0).collect();
+            assert_eq!(&trial, oracle);
+            let trial: VecDeque<_> = snapshot.iter().rev().map(|x| x.0).rev().collect();
+            assert_eq!(&trial, oracle);
+            for (idx, x) in oracle.iter().enumerate() {
+                assert_eq!(snapshot.get(idx).map(|x| x.0), Some(*x));
+            }
+            assert!(snapshot.get(oracle.len()).is_none());
+        }
+    }
+    assert_eq!(Rc::strong_count(&counter), 1);
+}
+
+#[test]
+fn snapshot_outlives_deque() {
+    let snapshot = {
+        let mut trial = PinnedDeque::<String>::with_capacity_per_chunk(2);
+        for x in 0..5 {
+            trial.push_back(x.to_string());
+        }
+        let snapshot = trial.snapshot();
+        trial.push_back("5".to_owned());
+        *trial.front_mut().unwrap() = "a".to_owned();
+        assert_eq!(format!("{trial:?}"), r#"["a", "1", "2", "3", "4", "5"]"#);
+        snapshot
+    };
+    assert_eq!(format!("{snapshot:?}"), r#"["0", "1", "2", "3", "4"]"#);
+    assert_eq!(snapshot.front().map(String::as_str), Some("0"));
+    assert_eq!(snapshot.back().map(String::as_str), Some("4"));
+}
+
+#[test]
+fn snapshot_is_send_and_sync() {
+    fn assert_send_sync<T: Send + Sync>(_: &T) {}
+    let trial: PinnedDeque<usize> = (0..10).collect();
+    let snapshot = trial.snapshot();
+    assert_send_sync(&snapshot);
+    let handle = std::thread::spawn(move || snapshot.iter().sum::<usize>());
+    assert_eq!(handle.join().unwrap(), 45);
+}
+
+#[test]
+fn mutating_through_mut_ref_leaves_snapshot() {
+    let mut trial: PinnedDeque<usize> = (0..10).collect();
+    let snapshot = trial.snapshot();
+    for x in &mut trial {
+        *x += 10;
+    }
+    assert!(trial.iter().copied().eq(10..20));
+    assert!(snapshot.iter().copied().eq(0..10));
+}
+
+#[test]
+fn chunks_are_reclaimed_after_snapshots_drop() {
+    let counter = Rc::new(());
+    let mut trial = PinnedDeque::<Rc<()>>::with_capacity_per_chunk(4);
+    for _ in 0..8 {
+        trial.push_back(counter.clone());
+    }
+    let addrs: Vec<*const Rc<()>> = trial.iter().map(|x| x as *const _).collect();
+    let snapshot = trial.snapshot();
+    // The popped element is a clone, and the snapshot keeps the original.
+    drop(trial.pop_back());
+    assert_eq!(Rc::strong_count(&counter), 9);
+    drop(snapshot);
+
+    // Elements stay where they are.
+    assert_eq!(trial.get_mut(0).unwrap() as *const _, addrs[0]);
+    assert_eq!(trial.back_mut().unwrap() as *const _, addrs[6]);
+    assert!(trial
+        .iter()
+        .map(|x| x as *const _)
+        .eq(addrs[..7].iter().copied()));
+    // The original popped before is dropped on reclaiming its chunk.
+    assert_eq!(Rc::strong_count(&counter), 8);
+    // Popped elements are not cloned.
+    drop(trial.pop_front());
+    assert_eq!(Rc::strong_count(&counter), 7);
+    drop(trial.pop_back());
+    assert_eq!(Rc::strong_count(&counter), 6);
+    drop(trial);
+    assert_eq!(Rc::strong_count(&counter), 1);
+}
+
+#[test]
+fn clearing_while_snapshots_drop_on_other_threads() {
+    let counter = std::sync::Arc::new(());
+    for _ in 0..100 {
+        let mut trial = PinnedDeque::with_capacity_per_chunk(4);
+        for _ in 0..64 {
+            trial.push_back(counter.clone());
+        }
+        let snapshot = trial.snapshot();
+        let handle = std::thread::spawn(move || drop(snapshot));
+        trial.clear();
+        trial.push_back(counter.clone());
+        handle.join().unwrap();
+        drop(trial);
+        // Nothing is leaked, however the deque and the snapshot race.
+        assert_eq!(std::sync::Arc::strong_count(&counter), 1);
+    }
+}
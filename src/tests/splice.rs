@@ -0,0 +1,154 @@
+use super::*;
+use crate::*;
+use quickcheck_macros::quickcheck;
+use std::collections::VecDeque;
+
+fn build(ops: Vec<Op>) -> (VecDeque<usize>, PinnedDeque<usize>) {
+    let mut oracle = VecDeque::new();
+    let mut trial = PinnedDeque::with_capacity_per_chunk(3);
+    for op in ops.into_iter() {
+        match op {
+            Op::PushBack(n) => {
+                oracle.push_back(n);
+                trial.push_back(n);
+            }
+            Op::PushFront(n) => {
+                oracle.push_front(n);
+                trial.push_front(n);
+            }
+            Op::PopBack => {
+                oracle.pop_back();
+                trial.pop_back();
+            }
+            Op::PopFront => {
+                oracle.pop_front();
+                trial.pop_front();
+            }
+        }
+    }
+    (oracle, trial)
+}
+
+fn check(oracle: &VecDeque<usize>, trial: &PinnedDeque<usize>) {
+    assert_eq!(trial.len(), oracle.len());
+    assert!(trial.iter().eq(oracle.iter()));
+    assert!(trial.iter().rev().eq(oracle.iter().rev()));
+    for (i, x) in oracle.iter().enumerate() {
+        assert_eq!(trial.get(i), Some(x));
+        assert_eq!(trial.index_of(trial.get(i).unwrap()), Some(i));
+    }
+}
+
+#[quickcheck]
+fn append(xs: Vec<Op>, ys: Vec<Op>) {
+    let (mut oracle, mut trial) = build(xs);
+    let (mut other_oracle, mut other) = build(ys);
+    let end_seq = other.end_seq();
+    oracle.append(&mut other_oracle);
+    trial.append(&mut other);
+    check(&oracle, &trial);
+    assert!(other.is_empty());
+    assert_eq!(other.first_seq(), end_seq);
+    other.push_back(1);
+    assert!(other.iter().eq(&[1]));
+}
+
+#[test]
+fn append_splices_chunks() {
+    let mut trial = PinnedDeque::with_capacity_per_chunk(4);
+    trial.extend(0..8);
+    let mut other = PinnedDeque::with_capacity_per_chunk(4);
+    other.extend(8..20);
+    let addrs: Vec<_> = other.iter().map(|x| x as *const i32).collect();
+    trial.append(&mut other);
+    assert!(trial.iter().eq((0..20).collect::<Vec<_>>().iter()));
+    assert!(trial.iter().skip(8).map(|x| x as *const i32).eq(addrs));
+    assert_eq!(other.stats().used_chunks, 0);
+}
+
+#[test]
+fn append_with_partial_back_chunk() {
+    // Only the back chunk of `trial` is moved.
+    let mut trial = PinnedDeque::with_capacity_per_chunk(4);
+    trial.extend(0..2);
+    let mut other = PinnedDeque::with_capacity_per_chunk(4);
+    other.extend(2..14);
+    let addrs: Vec<_> = other.iter().map(|x| x as *const i32).collect();
+    trial.append(&mut other);
+    assert!(trial.iter().eq((0..14).collect::<Vec<_>>().iter()));
+    assert!(trial.iter().skip(2).map(|x| x as *const i32).eq(addrs));
+    assert_eq!(trial.first_seq(), 0);
+    assert_eq!(other.stats().used_chunks, 0);
+    assert_eq!(other.first_seq(), 12);
+
+    // Only the elements of `other` are moved.
+    let mut trial = PinnedDeque::with_capacity_per_chunk(4);
+    trial.extend(0..10);
+    let addrs: Vec<_> = trial.iter().map(|x| x as *const i32).collect();
+    let mut other = PinnedDeque::with_capacity_per_chunk(4);
+    other.extend(10..12);
+    trial.append(&mut other);
+    assert!(trial.iter().eq((0..12).collect::<Vec<_>>().iter()));
+    assert!(trial.iter().take(10).map(|x| x as *const i32).eq(addrs));
+}
+
+#[quickcheck]
+fn split_off(xs: Vec<Op>, at: usize) {
+    let (mut oracle, mut trial) = build(xs);
+    let at = if oracle.is_empty() {
+        0
+    } else {
+        at % (oracle.len() + 1)
+    };
+    let first_seq = trial.first_seq();
+    let addrs: Vec<_> = trial.iter().map(|x| x as *const usize).collect();
+    let other_oracle = oracle.split_off(at);
+    let other = trial.split_off(at);
+    check(&oracle, &trial);
+    check(&other_oracle, &other);
+    assert_eq!(other.first_seq(), first_seq.wrapping_add(at as u64));
+    // Elements in chunks after the one holding `at` stay pinned.
+    for (i, x) in other.iter().enumerate() {
+        if i >= 3 {
+            assert_eq!(x as *const usize, addrs[at + i]);
+        }
+    }
+    let mut trial = trial;
+    let mut other = other;
+    trial.append(&mut other);
+    assert!(trial
+        .iter()
+        .map(|x| x as *const usize)
+        .take(at)
+        .eq(addrs[..at].iter().copied()));
+    oracle.extend(other_oracle);
+    check(&oracle, &trial);
+}
+
+#[test]
+fn split_off_on_boundary() {
+    let mut trial = PinnedDeque::with_capacity_per_chunk(4);
+    trial.extend(0..10);
+    let addrs: Vec<_> = trial.iter().map(|x| x as *const i32).collect();
+    let other = trial.split_off(4);
+    assert!(trial.iter().eq(&[0, 1, 2, 3]));
+    assert!(other
+        .iter()
+        .map(|x| x as *const i32)
+        .eq(addrs[4..].iter().copied()));
+    assert_eq!(other.stats().used_chunks, 2);
+}
+
+#[test]
+fn splice_shared_chunks() {
+    let mut trial = PinnedDeque::with_capacity_per_chunk(4);
+    trial.extend(0..10);
+    let snapshot = trial.snapshot();
+    let mut other = trial.split_off(4);
+    other.pop_back();
+    *other.front_mut().unwrap() = 100;
+    trial.append(&mut other);
+    *trial.get_mut(5).unwrap() = 200;
+    assert!(snapshot.iter().eq((0..10).collect::<Vec<_>>().iter()));
+    assert!(trial.iter().eq(&[0, 1, 2, 3, 100, 200, 6, 7, 8]));
+}
@@ -0,0 +1,112 @@
+use crate::*;
+
+#[test]
+fn stats() {
+    let mut trial = PinnedDeque::<usize>::with_capacity_per_chunk(4);
+    assert_eq!(
+        trial.stats(),
+        ChunkStats {
+            used_chunks: 0,
+            free_chunks: 0,
+            cap_per_chunk: 4,
+            front_slack: 0,
+            back_slack: 0,
+        }
+    );
+    trial.push_front(0);
+    for x in 1..6 {
+        trial.push_back(x);
+    }
+    assert_eq!(
+        trial.stats(),
+        ChunkStats {
+            used_chunks: 3,
+            free_chunks: 0,
+            cap_per_chunk: 4,
+            front_slack: 3,
+            back_slack: 3,
+        }
+    );
+    assert_eq!(trial.layout(), vec![3..4, 0..4, 0..1]);
+    trial.pop_back();
+    assert_eq!(trial.stats().used_chunks, 2);
+    assert_eq!(trial.stats().free_chunks, 1);
+    assert_eq!(trial.layout(), vec![3..4, 0..4]);
+}
+
+#[test]
+fn memory_usage() {
+    let mut trial = PinnedDeque::<u64>::with_capacity_per_chunk(4);
+    assert_eq!(trial.memory_usage().used_chunks, 0);
+    assert_eq!(trial.memory_usage().freed_chunks, 0);
+    trial.reserve(8);
+    let usage = trial.memory_usage();
+    assert_eq!(usage.used_chunks, 0);
+    assert!(usage.freed_chunks >= 2 * 4 * 8);
+    trial.push_back(0);
+    let usage = trial.memory_usage();
+    assert_eq!(usage.used_chunks, usage.freed_chunks);
+    assert!(usage.bookkeeping > 0);
+    assert_eq!(
+        usage.total(),
+        usage.used_chunks + usage.freed_chunks + usage.bookkeeping
+    );
+}
+
+#[test]
+fn small_chunks_are_not_padded() {
+    let mut trial = PinnedDeque::<u64>::with_capacity_per_chunk(4);
+    trial.reserve(8);
+    assert_eq!(trial.memory_usage().freed_chunks, 2 * (8 + 4 * 8));
+    assert_eq!(trial.release_idle_memory(), 0);
+    assert_eq!(trial.memory_usage().released_chunks, 0);
+}
+
+#[test]
+fn release_idle_memory() {
+    // Chunks fit into a page by default.
+    let mut trial = PinnedDeque::<u64>::new();
+    let cap = trial.stats().cap_per_chunk as u64;
+    trial.extend(0..4 * cap);
+    for _ in 0..2 * cap {
+        trial.pop_front();
+    }
+    let chunk_size = trial.memory_usage().used_chunks / 2;
+    assert_eq!(trial.release_idle_memory(), 2 * chunk_size);
+    assert_eq!(trial.memory_usage().released_chunks, 2 * chunk_size);
+    assert_eq!(trial.release_idle_memory(), 0);
+    trial.push_back(4 * cap);
+    assert_eq!(trial.memory_usage().released_chunks, chunk_size);
+    for x in 4 * cap + 1..5 * cap + 1 {
+        trial.push_back(x);
+    }
+    assert_eq!(trial.memory_usage().released_chunks, 0);
+    assert!(trial.iter().copied().eq(2 * cap..5 * cap + 1));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn release_idle_memory_automatically() {
+    let mut trial = PinnedDeque::<u64>::new();
+    let cap = trial.stats().cap_per_chunk as u64;
+    trial.set_idle_release_period(Some(std::time::Duration::ZERO));
+    trial.extend(0..2 * cap);
+    for _ in 0..cap {
+        trial.pop_front();
+    }
+    assert_eq!(
+        trial.memory_usage().released_chunks,
+        trial.memory_usage().freed_chunks
+    );
+    assert_ne!(trial.memory_usage().released_chunks, 0);
+    trial.set_idle_release_period(None);
+    for _ in 0..cap {
+        trial.pop_front();
+    }
+    assert_ne!(
+        trial.memory_usage().released_chunks,
+        trial.memory_usage().freed_chunks
+    );
+    trial.extend(0..2 * cap);
+    assert!(trial.iter().copied().eq(0..2 * cap));
+}
@@ -0,0 +1,230 @@
+use super::*;
+use crate::*;
+use quickcheck_macros::quickcheck;
+use std::{collections::VecDeque, thread};
+
+fn build(ops: Vec<Op>) -> (VecDeque<usize>, PinnedDeque<usize>) {
+    let mut oracle = VecDeque::new();
+    let mut trial = PinnedDeque::with_capacity_per_chunk(3);
+    for op in ops.into_iter() {
+        match op {
+            Op::PushBack(n) => {
+                oracle.push_back(n);
+                trial.push_back(n);
+            }
+            Op::PushFront(n) => {
+                oracle.push_front(n);
+                trial.push_front(n);
+            }
+            Op::PopBack => {
+                oracle.pop_back();
+                trial.pop_back();
+            }
+            Op::PopFront => {
+                oracle.pop_front();
+                trial.pop_front();
+            }
+        }
+    }
+    (oracle, trial)
+}
+
+#[quickcheck]
+fn split_at(ops: Vec<Op>, mid: usize, mid2: usize) {
+    let (oracle, trial) = build(ops);
+    let mid = mid % (oracle.len() + 1);
+    let (front, back) = trial.split_at(mid);
+    let oracle: Vec<_> = oracle.into_iter().collect();
+    let (oracle_front, oracle_back) = oracle.split_at(mid);
+    for (view, oracle) in [(front, oracle_front), (back, oracle_back)] {
+        assert_eq!(view.len(), oracle.len());
+        assert!(view.iter().eq(oracle.iter()));
+        assert!(view.iter().rev().eq(oracle.iter().rev()));
+        for i in 0..=oracle.len() {
+            assert_eq!(view.get(i), oracle.get(i));
+        }
+        let mid2 = mid2 % (oracle.len() + 1);
+        let (a, b) = view.split_at(mid2);
+        assert!(a.iter().chain(b).eq(oracle.iter()));
+        assert_eq!(b.iter().len(), oracle.len() - mid2);
+    }
+}
+
+#[quickcheck]
+fn split_at_mut(ops: Vec<Op>, mid: usize, mid2: usize) {
+    let (oracle, mut trial) = build(ops);
+    let mid = mid % (oracle.len() + 1);
+    let (mut front, mut back) = trial.split_at_mut(mid);
+    for x in front.iter_mut() {
+        *x = x.wrapping_add(1);
+    }
+    for x in back.iter_mut().rev() {
+        *x = x.wrapping_add(2);
+    }
+    let mid2 = mid2 % (back.len() + 1);
+    let (mut a, mut b) = back.split_at_mut(mid2);
+    if let Some(x) = a.get_mut(0) {
+        *x = x.wrapping_add(10);
+    }
+    if let Some(x) = b.get_mut(b.len().wrapping_sub(1)) {
+        *x = x.wrapping_add(20);
+    }
+    assert!(b.get_mut(b.len()).is_none());
+    let mut expected: Vec<_> = oracle
+        .iter()
+        .enumerate()
+        .map(|(i, x)| x.wrapping_add(if i < mid { 1 } else { 2 }))
+        .collect();
+    if mid2 > 0 {
+        expected[mid] = expected[mid].wrapping_add(10);
+    }
+    if mid2 < oracle.len() - mid {
+        let last = oracle.len() - 1;
+        expected[last] = expected[last].wrapping_add(20);
+    }
+    assert!(trial.iter().eq(expected.iter()));
+}
+
+#[test]
+fn update_halves_from_threads() {
+    let mut trial = PinnedDeque::<u64>::with_capacity_per_chunk(5);
+    trial.extend(0..23);
+    trial.pop_front();
+    let (mut front, mut back) = trial.split_at_mut(11);
+    assert_eq!(format!("{:?}", front.split_at_mut(2).0), "[1, 2]");
+    thread::scope(|s| {
+        s.spawn(|| front.iter_mut().for_each(|x| *x *= 2));
+        s.spawn(|| {
+            for x in &mut back {
+                *x += 100;
+            }
+        });
+    });
+    let expected: Vec<_> = (1..23)
+        .map(|x| if x < 12 { x * 2 } else { x + 100 })
+        .collect();
+    assert!(trial.iter().eq(expected.iter()));
+}
+
+#[test]
+fn split_at_mut_with_snapshot() {
+    let mut trial = PinnedDeque::<i32>::with_capacity_per_chunk(4);
+    trial.extend(0..10);
+    let snapshot = trial.snapshot();
+    let (mut front, _) = trial.split_at_mut(5);
+    *front.get_mut(4).unwrap() = 100;
+    assert!(snapshot.iter().eq((0..10).collect::<Vec<_>>().iter()));
+    assert_eq!(trial.get(4), Some(&100));
+}
+
+#[test]
+#[should_panic]
+fn split_at_out_of_bounds() {
+    let trial: PinnedDeque<i32> = [1, 2, 3].into();
+    trial.split_at(4);
+}
+
+#[quickcheck]
+fn slice(ops: Vec<Op>, a: usize, b: usize, c: usize, d: usize) {
+    let (oracle, mut trial) = build(ops);
+    let oracle: Vec<_> = oracle.into_iter().collect();
+    let len = oracle.len();
+    let (start, end) = {
+        let (a, b) = (a % (len + 1), b % (len + 1));
+        (a.min(b), a.max(b))
+    };
+    let view = trial.slice(start..end);
+    let expected = &oracle[start..end];
+    assert_eq!(view.len(), expected.len());
+    assert_eq!(view.first(), expected.first());
+    assert_eq!(view.last(), expected.last());
+    assert!(view.iter().eq(expected.iter()));
+    assert!(view == *expected);
+    for i in 0..=expected.len() {
+        assert_eq!(view.get(i), expected.get(i));
+    }
+    let (c, d) = {
+        let (c, d) = (c % (expected.len() + 1), d % (expected.len() + 1));
+        (c.min(d), c.max(d))
+    };
+    if c < d {
+        assert!(view.slice(c..=d - 1) == expected[c..d]);
+    }
+    assert!(view.slice(c..d) == expected[c..d]);
+    assert!(trial.slice(..) == trial);
+
+    let mut view = trial.slice_mut(start..end);
+    for i in 0..view.len() {
+        view[i] = view[i].wrapping_add(1);
+    }
+    if let Some(x) = view.last_mut() {
+        *x = 0;
+    }
+    let mut expected = oracle.clone();
+    for x in expected[start..end].iter_mut() {
+        *x = x.wrapping_add(1);
+    }
+    if start < end {
+        expected[end - 1] = 0;
+    }
+    assert!(trial.slice(..) == expected);
+    for i in 0..len {
+        assert_eq!(trial[i], expected[i]);
+    }
+}
+
+#[test]
+fn slice_cmp() {
+    use std::{collections::hash_map::RandomState, hash::BuildHasher};
+    let mut trial = PinnedDeque::<i32>::with_capacity_per_chunk(3);
+    trial.extend([1, 2, 3, 1, 2, 4]);
+    let a = trial.slice(..3);
+    let b = trial.slice(3..);
+    assert!(a == [1, 2, 3]);
+    assert!(a == vec![1, 2, 3]);
+    assert!(a != b);
+    assert!(a < b);
+    assert_eq!(a.cmp(&b), std::cmp::Ordering::Less);
+    assert!(trial.slice(..2) == trial.slice(3..5));
+    let state = RandomState::new();
+    assert_eq!(state.hash_one(trial.slice(..)), state.hash_one(&trial));
+    assert_eq!(
+        state.hash_one(trial.slice(1..3)),
+        state.hash_one(VecDeque::from([2, 3]))
+    );
+    assert_eq!(format!("{:?}", trial.slice(1..=2)), "[2, 3]");
+
+    let (mut x, mut y) = trial.split_at_mut(3);
+    x[0] = 10;
+    y[0] = 10;
+    assert!(x.slice(..2) == y.slice(..2).slice(..));
+    assert!(x.slice_mut(..2) == y.slice_mut(..2));
+    assert!(x != y);
+}
+
+#[test]
+fn index_deque() {
+    let mut trial = PinnedDeque::<i32>::with_capacity_per_chunk(3);
+    trial.extend(0..10);
+    let snapshot = trial.snapshot();
+    trial[5] += 100;
+    assert_eq!(trial[5], 105);
+    assert_eq!(snapshot.iter().nth(5), Some(&5));
+    let view = trial.slice_mut(4..7);
+    assert_eq!(view[1], 105);
+    assert_eq!(snapshot.iter().nth(4), Some(&4));
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds: the len is 3 but the index is 3")]
+fn index_out_of_bounds() {
+    let trial: PinnedDeque<i32> = [1, 2, 3].into();
+    let _ = trial[3];
+}
+
+#[test]
+#[should_panic]
+fn slice_out_of_bounds() {
+    let trial: PinnedDeque<i32> = [1, 2, 3].into();
+    trial.slice(1..4);
+}
@@ -0,0 +1,106 @@
+use super::*;
+use crate::*;
+use quickcheck_macros::quickcheck;
+use std::collections::VecDeque;
+
+fn build(ops: Vec<Op>) -> (Vec<usize>, PinnedDeque<usize>) {
+    let mut oracle = VecDeque::new();
+    let mut trial = PinnedDeque::with_capacity_per_chunk(4);
+    for op in ops.into_iter() {
+        match op {
+            Op::PushBack(n) => {
+                oracle.push_back(n);
+                trial.push_back(n);
+            }
+            Op::PushFront(n) => {
+                oracle.push_front(n);
+                trial.push_front(n);
+            }
+            Op::PopBack => {
+                oracle.pop_back();
+                trial.pop_back();
+            }
+            Op::PopFront => {
+                oracle.pop_front();
+                trial.pop_front();
+            }
+        }
+    }
+    (oracle.into_iter().collect(), trial)
+}
+
+#[quickcheck]
+fn windows(ops: Vec<Op>, size: usize) {
+    let (oracle, trial) = build(ops);
+    let size = size % 10 + 1;
+    let windows = trial.windows(size);
+    assert_eq!(windows.len(), oracle.windows(size).len());
+    for (window, expected) in windows.clone().zip(oracle.windows(size)) {
+        assert_eq!(window.len(), size);
+        assert!(window == *expected);
+        assert!(window.iter().eq(expected.iter()));
+        assert_eq!(window.first(), expected.first());
+        assert_eq!(window.last(), expected.last());
+        assert_eq!(window[size - 1], expected[size - 1]);
+        if let Some(elems) = window.as_slice() {
+            assert_eq!(elems, expected);
+        } else {
+            assert!(size > 1);
+        }
+    }
+    assert!(windows
+        .rev()
+        .map(|w| w.iter().copied().collect::<Vec<_>>())
+        .eq(oracle.windows(size).rev().map(|w| w.to_vec())));
+}
+
+#[quickcheck]
+fn array_windows(ops: Vec<Op>) {
+    let (oracle, trial) = build(ops);
+    assert!(trial
+        .array_windows::<3>()
+        .eq(oracle.windows(3).map(|w| [w[0], w[1], w[2]])));
+    assert!(trial
+        .array_windows::<1>()
+        .rev()
+        .eq(oracle.iter().rev().map(|x| [*x])));
+    assert_eq!(trial.array_windows::<6>().len(), oracle.windows(6).len());
+}
+
+#[quickcheck]
+fn array_chunks(ops: Vec<Op>) {
+    let (oracle, trial) = build(ops);
+    let chunks = trial.array_chunks::<3>();
+    let expected = oracle.chunks_exact(3);
+    assert!(chunks.remainder() == *expected.remainder());
+    assert_eq!(chunks.len(), expected.len());
+    assert!(chunks
+        .clone()
+        .eq(expected.clone().map(|c| [c[0], c[1], c[2]])));
+    assert!(chunks.rev().eq(expected.rev().map(|c| [c[0], c[1], c[2]])));
+    assert!(trial
+        .array_chunks::<4>()
+        .flatten()
+        .eq(oracle.iter().copied().take(oracle.len() / 4 * 4)));
+}
+
+#[test]
+fn window_crosses_boundary() {
+    let mut trial = PinnedDeque::<i32>::with_capacity_per_chunk(4);
+    trial.extend(0..10);
+    let windows: Vec<_> = trial.windows(3).collect();
+    assert_eq!(windows.len(), 8);
+    assert_eq!(windows[0].as_slice(), Some(&[0, 1, 2][..]));
+    assert!(windows[2].as_slice().is_none());
+    assert!(windows[2] == [2, 3, 4]);
+    assert_eq!(format!("{:?}", windows[3]), "[3, 4, 5]");
+    assert!(trial.windows(11).next().is_none());
+    assert!(trial.windows(10).all(|w| w.as_slice().is_none()));
+}
+
+#[test]
+#[should_panic]
+fn zero_sized_windows() {
+    let trial: PinnedDeque<i32> = [1, 2, 3].into();
+    trial.windows(0);
+}
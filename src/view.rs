@@ -0,0 +1,741 @@
+use crate::{chunk::Chunk, *};
+use alloc::{
+    collections::{vec_deque, VecDeque},
+    vec::Vec,
+};
+use core::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    iter::FusedIterator,
+    marker::PhantomData,
+    ops::{Bound, Index, IndexMut, Range, RangeBounds},
+    ptr::{self, NonNull},
+    slice,
+};
+
+/// A range of elements in a deque, located by the same arithmetic as `PinnedDeque::get()`.
+pub(crate) struct Span<'a, T: Sized> {
+    chunks: &'a VecDeque<*mut Chunk<T>>,
+    cap_per_chunk: u32,
+    cap_shift: Option<u32>,
+    /// The position of the first element, counting from the beginning of the first chunk.
+    offset: usize,
+    pub(crate) len: usize,
+}
+
+impl<T: Sized> Clone for Span<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Sized> Copy for Span<'_, T> {}
+
+impl<'a, T: Sized> Span<'a, T> {
+    pub(crate) fn new(deque: &'a PinnedDeque<T>) -> Self {
+        let offset = match deque.used.front() {
+            Some(front_chunk) => unsafe { &**front_chunk }.start as usize,
+            None => 0,
+        };
+        Self {
+            chunks: &deque.used,
+            cap_per_chunk: deque.cap_per_chunk,
+            cap_shift: deque.cap_shift,
+            offset,
+            len: deque.len(),
+        }
+    }
+
+    /// Returns the position of a chunk in the deque and the slot in it.
+    fn locate(&self, pos: usize) -> (usize, usize) {
+        match self.cap_shift {
+            Some(shift) => (pos >> shift, pos & ((1 << shift) - 1)),
+            None => {
+                let cap_per_chunk = self.cap_per_chunk as usize;
+                (pos / cap_per_chunk, pos % cap_per_chunk)
+            }
+        }
+    }
+
+    pub(crate) fn get(&self, idx: usize) -> Option<*mut T> {
+        if idx >= self.len {
+            return None;
+        }
+        let (n, slot) = self.locate(self.offset + idx);
+        let chunk = unsafe { *self.chunks.get(n).unwrap_unchecked() };
+        Some(unsafe { Chunk::elems(chunk).add(slot) as *mut T })
+    }
+
+    pub(crate) fn slice(&self, range: impl RangeBounds<usize>) -> Self {
+        let range = to_range(range, self.len);
+        Self {
+            offset: self.offset + range.start,
+            len: range.len(),
+            ..*self
+        }
+    }
+
+    fn split_at(&self, mid: usize) -> (Self, Self) {
+        assert!(mid <= self.len, "`mid` out of bounds");
+        let front = Self { len: mid, ..*self };
+        let back = Self {
+            offset: self.offset + mid,
+            len: self.len - mid,
+            ..*self
+        };
+        (front, back)
+    }
+
+    /// Returns the elements as a single slice, if they lie in a single chunk.
+    pub(crate) fn as_contiguous(&self) -> Option<*mut [T]> {
+        let (first, _) = self.locate(self.offset);
+        let (last, _) = self.locate(self.offset + self.len.max(1) - 1);
+        if first == last {
+            Some(self.parts().0)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the slots of the first and last chunks, and the chunks in between.
+    fn parts(&self) -> (*mut [T], vec_deque::Iter<'a, *mut Chunk<T>>, *mut [T]) {
+        if self.len == 0 {
+            let empty = ptr::slice_from_raw_parts_mut(NonNull::dangling().as_ptr(), 0);
+            return (empty, self.chunks.range(0..0), empty);
+        }
+        let (first, start) = self.locate(self.offset);
+        let (last, end) = self.locate(self.offset + self.len - 1);
+        let slots = |n: usize, start: usize, end: usize| -> *mut [T] {
+            let chunk = unsafe { *self.chunks.get(n).unwrap_unchecked() };
+            let elems = unsafe { Chunk::elems(chunk).add(start) as *mut T };
+            ptr::slice_from_raw_parts_mut(elems, end - start)
+        };
+        if first == last {
+            let front = slots(first, start, end + 1);
+            let back = slots(last, end + 1, end + 1);
+            (front, self.chunks.range(first + 1..first + 1), back)
+        } else {
+            let front = slots(first, start, self.cap_per_chunk as usize);
+            let back = slots(last, 0, end + 1);
+            (front, self.chunks.range(first + 1..last), back)
+        }
+    }
+}
+
+/// Resolves `range` into `[start, end)`, panicking like slice indexing if it is out of bounds.
+fn to_range(range: impl RangeBounds<usize>, len: usize) -> Range<usize> {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start.checked_add(1).expect("range start overflows"),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end.checked_add(1).expect("range end overflows"),
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+    assert!(start <= end, "range starts at {start} but ends at {end}");
+    assert!(end <= len, "range end {end} out of range for length {len}");
+    start..end
+}
+
+/// A shared view of a range of elements in a deque.
+pub struct DequeView<'a, T: Sized> {
+    span: Span<'a, T>,
+    _marker: PhantomData<&'a T>,
+}
+
+/// A mutable view of a range of elements in a deque.
+///
+/// Views split from the same deque hold disjoint elements, so they can be used from different
+/// threads at the same time.
+pub struct DequeViewMut<'a, T: Sized> {
+    span: Span<'a, T>,
+    _marker: PhantomData<&'a mut T>,
+}
+
+/// A shared slice of a deque, i.e., a view returned by `slice()`.
+pub type DequeSlice<'a, T> = DequeView<'a, T>;
+
+/// A mutable slice of a deque, i.e., a view returned by `slice_mut()`.
+pub type DequeSliceMut<'a, T> = DequeViewMut<'a, T>;
+
+unsafe impl<T: Sync> Send for DequeView<'_, T> {}
+unsafe impl<T: Sync> Sync for DequeView<'_, T> {}
+unsafe impl<T: Send> Send for DequeViewMut<'_, T> {}
+unsafe impl<T: Sync> Sync for DequeViewMut<'_, T> {}
+
+impl<T> PinnedDeque<T>
+where
+    T: Sized,
+{
+    /// Divides the deque into two shared views at `mid`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mid > len`.
+    pub fn split_at(&self, mid: usize) -> (DequeView<'_, T>, DequeView<'_, T>) {
+        let (front, back) = Span::new(self).split_at(mid);
+        (DequeView::new(front), DequeView::new(back))
+    }
+
+    /// Divides the deque into two mutable views at `mid`.
+    ///
+    /// Chunks shared with snapshots are copied first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mid > len`.
+    pub fn split_at_mut(&mut self, mid: usize) -> (DequeViewMut<'_, T>, DequeViewMut<'_, T>) {
+        assert!(mid <= self.len(), "`mid` out of bounds");
+        self.unshare_all();
+        let (front, back) = Span::new(self).split_at(mid);
+        (DequeViewMut::new(front), DequeViewMut::new(back))
+    }
+
+    /// Returns a shared slice of elements in `range`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds.
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> DequeSlice<'_, T> {
+        DequeView::new(Span::new(self).slice(range))
+    }
+
+    /// Returns a mutable slice of elements in `range`.
+    ///
+    /// Chunks in `range` shared with snapshots are copied first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds.
+    pub fn slice_mut(&mut self, range: impl RangeBounds<usize>) -> DequeSliceMut<'_, T> {
+        let range = to_range(range, self.len());
+        if !range.is_empty() {
+            let (first, _) = unsafe { self.locate_in_used(range.start).unwrap_unchecked() };
+            let (last, _) = unsafe { self.locate_in_used(range.end - 1).unwrap_unchecked() };
+            for n in first..=last {
+                self.unshare(n);
+            }
+        }
+        DequeViewMut::new(Span::new(self).slice(range))
+    }
+}
+
+impl<'a, T: Sized> DequeView<'a, T> {
+    pub(crate) fn new(span: Span<'a, T>) -> Self {
+        Self {
+            span,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.span.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, idx: usize) -> Option<&'a T> {
+        Some(unsafe { &*self.span.get(idx)? })
+    }
+
+    pub fn first(&self) -> Option<&'a T> {
+        self.get(0)
+    }
+
+    pub fn last(&self) -> Option<&'a T> {
+        self.get(self.len().wrapping_sub(1))
+    }
+
+    pub fn iter(&self) -> DequeViewIter<'a, T> {
+        DequeViewIter::new(self.span)
+    }
+
+    /// Returns a sub-slice of elements in `range`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds.
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> DequeSlice<'a, T> {
+        Self::new(self.span.slice(range))
+    }
+
+    /// Divides the view into two at `mid`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mid > len`.
+    pub fn split_at(&self, mid: usize) -> (DequeView<'a, T>, DequeView<'a, T>) {
+        let (front, back) = self.span.split_at(mid);
+        (Self::new(front), Self::new(back))
+    }
+}
+
+impl<'a, T: Sized> DequeViewMut<'a, T> {
+    fn new(span: Span<'a, T>) -> Self {
+        Self {
+            span,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.span.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, idx: usize) -> Option<&T> {
+        Some(unsafe { &*self.span.get(idx)? })
+    }
+
+    pub fn get_mut(&mut self, idx: usize) -> Option<&mut T> {
+        Some(unsafe { &mut *self.span.get(idx)? })
+    }
+
+    pub fn first(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    pub fn first_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(0)
+    }
+
+    pub fn last(&self) -> Option<&T> {
+        self.get(self.len().wrapping_sub(1))
+    }
+
+    pub fn last_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(self.len().wrapping_sub(1))
+    }
+
+    pub fn iter(&self) -> DequeViewIter<'_, T> {
+        DequeViewIter::new(self.span)
+    }
+
+    pub fn iter_mut(&mut self) -> DequeViewIterMut<'_, T> {
+        DequeViewIterMut::new(self.span)
+    }
+
+    /// Returns a shared sub-slice of elements in `range`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds.
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> DequeSlice<'_, T> {
+        DequeView::new(self.span.slice(range))
+    }
+
+    /// Returns a mutable sub-slice of elements in `range`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds.
+    pub fn slice_mut(&mut self, range: impl RangeBounds<usize>) -> DequeSliceMut<'_, T> {
+        DequeViewMut::new(self.span.slice(range))
+    }
+
+    fn as_view(&self) -> DequeView<'_, T> {
+        DequeView::new(self.span)
+    }
+
+    /// Divides the view into two mutable views at `mid`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mid > len`.
+    pub fn split_at_mut(&mut self, mid: usize) -> (DequeViewMut<'_, T>, DequeViewMut<'_, T>) {
+        let (front, back) = self.span.split_at(mid);
+        (DequeViewMut::new(front), DequeViewMut::new(back))
+    }
+}
+
+impl<T: Sized> Clone for DequeView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Sized> Copy for DequeView<'_, T> {}
+
+impl<T: Sized> Index<usize> for DequeView<'_, T> {
+    type Output = T;
+
+    fn index(&self, idx: usize) -> &T {
+        let len = self.len();
+        match self.get(idx) {
+            Some(elem) => elem,
+            None => panic!("index out of bounds: the len is {len} but the index is {idx}"),
+        }
+    }
+}
+
+impl<T: Sized> Index<usize> for DequeViewMut<'_, T> {
+    type Output = T;
+
+    fn index(&self, idx: usize) -> &T {
+        let len = self.len();
+        match self.get(idx) {
+            Some(elem) => elem,
+            None => panic!("index out of bounds: the len is {len} but the index is {idx}"),
+        }
+    }
+}
+
+impl<T: Sized> IndexMut<usize> for DequeViewMut<'_, T> {
+    fn index_mut(&mut self, idx: usize) -> &mut T {
+        let len = self.len();
+        match self.get_mut(idx) {
+            Some(elem) => elem,
+            None => panic!("index out of bounds: the len is {len} but the index is {idx}"),
+        }
+    }
+}
+
+impl<T, U> PartialEq<DequeView<'_, U>> for DequeView<'_, T>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &DequeView<'_, U>) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T, U> PartialEq<DequeViewMut<'_, U>> for DequeViewMut<'_, T>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &DequeViewMut<'_, U>) -> bool {
+        self.as_view() == other.as_view()
+    }
+}
+
+impl<T, U> PartialEq<PinnedDeque<U>> for DequeView<'_, T>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &PinnedDeque<U>) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T, U> PartialEq<[U]> for DequeView<'_, T>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &[U]) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T, U> PartialEq<[U]> for DequeViewMut<'_, T>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &[U]) -> bool {
+        self.as_view() == *other
+    }
+}
+
+impl<T, U, const N: usize> PartialEq<[U; N]> for DequeView<'_, T>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &[U; N]) -> bool {
+        *self == other[..]
+    }
+}
+
+impl<T, U, const N: usize> PartialEq<[U; N]> for DequeViewMut<'_, T>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &[U; N]) -> bool {
+        self.as_view() == other[..]
+    }
+}
+
+impl<T, U> PartialEq<Vec<U>> for DequeView<'_, T>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &Vec<U>) -> bool {
+        *self == other[..]
+    }
+}
+
+impl<T, U> PartialEq<Vec<U>> for DequeViewMut<'_, T>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &Vec<U>) -> bool {
+        self.as_view() == other[..]
+    }
+}
+
+impl<T: Eq> Eq for DequeView<'_, T> {}
+
+impl<T: Eq> Eq for DequeViewMut<'_, T> {}
+
+impl<T: PartialOrd> PartialOrd for DequeView<'_, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: PartialOrd> PartialOrd for DequeViewMut<'_, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.as_view().partial_cmp(&other.as_view())
+    }
+}
+
+impl<T: Ord> Ord for DequeView<'_, T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T: Ord> Ord for DequeViewMut<'_, T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_view().cmp(&other.as_view())
+    }
+}
+
+impl<T: Hash> Hash for DequeView<'_, T> {
+    /// Hashes the same as `PinnedDeque` and `VecDeque`.
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len());
+        for x in self.iter() {
+            x.hash(state);
+        }
+    }
+}
+
+impl<T: Hash> Hash for DequeViewMut<'_, T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_view().hash(state)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for DequeView<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for DequeViewMut<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+pub struct DequeViewIter<'a, T: Sized> {
+    size: usize,
+    chunk_iter: vec_deque::Iter<'a, *mut Chunk<T>>,
+    front: slice::Iter<'a, T>,
+    back: slice::Iter<'a, T>,
+}
+
+pub struct DequeViewIterMut<'a, T: Sized> {
+    size: usize,
+    chunk_iter: vec_deque::Iter<'a, *mut Chunk<T>>,
+    cap_per_chunk: usize,
+    front: slice::IterMut<'a, T>,
+    back: slice::IterMut<'a, T>,
+}
+
+impl<'a, T: Sized> DequeViewIter<'a, T> {
+    fn new(span: Span<'a, T>) -> Self {
+        let (front, chunk_iter, back) = span.parts();
+        unsafe {
+            Self {
+                size: span.len,
+                chunk_iter,
+                front: (*front).iter(),
+                back: (*back).iter(),
+            }
+        }
+    }
+
+    pub(crate) fn from_slice(elems: &'a [T]) -> Self {
+        Self {
+            size: elems.len(),
+            chunk_iter: Default::default(),
+            front: elems.iter(),
+            back: [].iter(),
+        }
+    }
+}
+
+impl<'a, T: Sized> DequeViewIterMut<'a, T> {
+    fn new(span: Span<'a, T>) -> Self {
+        let (front, chunk_iter, back) = span.parts();
+        unsafe {
+            Self {
+                size: span.len,
+                chunk_iter,
+                cap_per_chunk: span.cap_per_chunk as usize,
+                front: (*front).iter_mut(),
+                back: (*back).iter_mut(),
+            }
+        }
+    }
+
+    /// Chunks between the first and the last ones are full.
+    fn full_chunk(&self, chunk: *mut Chunk<T>) -> slice::IterMut<'a, T> {
+        unsafe {
+            let elems = Chunk::elems(chunk) as *mut T;
+            slice::from_raw_parts_mut(elems, self.cap_per_chunk).iter_mut()
+        }
+    }
+}
+
+impl<T: Sized> Clone for DequeViewIter<'_, T> {
+    fn clone(&self) -> Self {
+        Self {
+            size: self.size,
+            chunk_iter: self.chunk_iter.clone(),
+            front: self.front.clone(),
+            back: self.back.clone(),
+        }
+    }
+}
+
+impl<'a, T: Sized> Iterator for DequeViewIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(res) = self.front.next() {
+                self.size -= 1;
+                return Some(res);
+            }
+            if let Some(chunk) = self.chunk_iter.next() {
+                self.front = unsafe { &**chunk }.as_slice().iter();
+            } else {
+                let res = self.back.next()?;
+                self.size -= 1;
+                return Some(res);
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.size, Some(self.size))
+    }
+}
+
+impl<T: Sized> DoubleEndedIterator for DequeViewIter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(res) = self.back.next_back() {
+                self.size -= 1;
+                return Some(res);
+            }
+            if let Some(chunk) = self.chunk_iter.next_back() {
+                self.back = unsafe { &**chunk }.as_slice().iter();
+            } else {
+                let res = self.front.next_back()?;
+                self.size -= 1;
+                return Some(res);
+            }
+        }
+    }
+}
+
+impl<'a, T: Sized> Iterator for DequeViewIterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(res) = self.front.next() {
+                self.size -= 1;
+                return Some(res);
+            }
+            if let Some(chunk) = self.chunk_iter.next() {
+                self.front = self.full_chunk(*chunk);
+            } else {
+                let res = self.back.next()?;
+                self.size -= 1;
+                return Some(res);
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.size, Some(self.size))
+    }
+}
+
+impl<T: Sized> DoubleEndedIterator for DequeViewIterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(res) = self.back.next_back() {
+                self.size -= 1;
+                return Some(res);
+            }
+            if let Some(chunk) = self.chunk_iter.next_back() {
+                self.back = self.full_chunk(*chunk);
+            } else {
+                let res = self.front.next_back()?;
+                self.size -= 1;
+                return Some(res);
+            }
+        }
+    }
+}
+
+impl<T: Sized> ExactSizeIterator for DequeViewIter<'_, T> {}
+
+impl<T: Sized> ExactSizeIterator for DequeViewIterMut<'_, T> {}
+
+impl<T: Sized> FusedIterator for DequeViewIter<'_, T> {}
+
+impl<T: Sized> FusedIterator for DequeViewIterMut<'_, T> {}
+
+unsafe impl<T: Sync> Send for DequeViewIter<'_, T> {}
+unsafe impl<T: Sync> Sync for DequeViewIter<'_, T> {}
+unsafe impl<T: Send> Send for DequeViewIterMut<'_, T> {}
+unsafe impl<T: Sync> Sync for DequeViewIterMut<'_, T> {}
+
+impl<'a, T: Sized> IntoIterator for DequeView<'a, T> {
+    type Item = &'a T;
+    type IntoIter = DequeViewIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T: Sized> IntoIterator for DequeViewMut<'a, T> {
+    type Item = &'a mut T;
+    type IntoIter = DequeViewIterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        DequeViewIterMut::new(self.span)
+    }
+}
+
+impl<'a, T: Sized> IntoIterator for &'a DequeViewMut<'_, T> {
+    type Item = &'a T;
+    type IntoIter = DequeViewIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T: Sized> IntoIterator for &'a mut DequeViewMut<'_, T> {
+    type Item = &'a mut T;
+    type IntoIter = DequeViewIterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
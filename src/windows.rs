@@ -0,0 +1,328 @@
+use crate::{view::Span, *};
+use core::{array, fmt, iter::FusedIterator, ops::Index};
+
+/// A window yielded by [`PinnedDeque::windows()`].
+pub enum Window<'a, T: Sized> {
+    /// The window lies in a single chunk.
+    Contiguous(&'a [T]),
+    /// The window crosses a chunk boundary.
+    Split(DequeSlice<'a, T>),
+}
+
+impl<'a, T: Sized> Window<'a, T> {
+    fn new(span: Span<'a, T>) -> Self {
+        match span.as_contiguous() {
+            Some(elems) => Self::Contiguous(unsafe { &*elems }),
+            None => Self::Split(DequeView::new(span)),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Contiguous(elems) => elems.len(),
+            Self::Split(view) => view.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, idx: usize) -> Option<&'a T> {
+        match self {
+            Self::Contiguous(elems) => elems.get(idx),
+            Self::Split(view) => view.get(idx),
+        }
+    }
+
+    pub fn first(&self) -> Option<&'a T> {
+        self.get(0)
+    }
+
+    pub fn last(&self) -> Option<&'a T> {
+        self.get(self.len().wrapping_sub(1))
+    }
+
+    pub fn iter(&self) -> DequeViewIter<'a, T> {
+        match self {
+            Self::Contiguous(elems) => DequeViewIter::from_slice(elems),
+            Self::Split(view) => view.iter(),
+        }
+    }
+
+    /// Returns the window as a slice, unless it crosses a chunk boundary.
+    pub fn as_slice(&self) -> Option<&'a [T]> {
+        match self {
+            Self::Contiguous(elems) => Some(elems),
+            Self::Split(_) => None,
+        }
+    }
+}
+
+impl<T: Sized> Clone for Window<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Sized> Copy for Window<'_, T> {}
+
+impl<T: Sized> Index<usize> for Window<'_, T> {
+    type Output = T;
+
+    fn index(&self, idx: usize) -> &T {
+        match self {
+            Self::Contiguous(elems) => &elems[idx],
+            Self::Split(view) => &view[idx],
+        }
+    }
+}
+
+impl<T, U> PartialEq<[U]> for Window<'_, T>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &[U]) -> bool {
+        match self {
+            Self::Contiguous(elems) => *elems == other,
+            Self::Split(view) => *view == *other,
+        }
+    }
+}
+
+impl<T, U, const N: usize> PartialEq<[U; N]> for Window<'_, T>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &[U; N]) -> bool {
+        *self == other[..]
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Window<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, T: Sized> IntoIterator for Window<'a, T> {
+    type Item = &'a T;
+    type IntoIter = DequeViewIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T> PinnedDeque<T>
+where
+    T: Sized,
+{
+    /// Returns an iterator over all overlapping windows of `size` elements.
+    ///
+    /// A window is a plain slice, unless it crosses a chunk boundary.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn windows(&self, size: usize) -> Windows<'_, T> {
+        assert!(size > 0, "window size must be non-zero");
+        let span = Span::new(self);
+        let back = (span.len + 1).saturating_sub(size);
+        Windows {
+            span,
+            size,
+            front: 0,
+            back,
+        }
+    }
+
+    /// Returns an iterator over all overlapping windows of `N` elements, copied into arrays.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is 0.
+    pub fn array_windows<const N: usize>(&self) -> ArrayWindows<'_, T, N>
+    where
+        T: Copy,
+    {
+        ArrayWindows(self.windows(N))
+    }
+
+    /// Returns an iterator over `N` elements at a time, copied into arrays.
+    ///
+    /// The last `len % N` elements are left out, which `ArrayChunks::remainder()` returns.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is 0.
+    pub fn array_chunks<const N: usize>(&self) -> ArrayChunks<'_, T, N>
+    where
+        T: Copy,
+    {
+        assert!(N > 0, "chunk size must be non-zero");
+        let span = Span::new(self);
+        ArrayChunks {
+            span,
+            front: 0,
+            back: span.len / N,
+        }
+    }
+}
+
+pub struct Windows<'a, T: Sized> {
+    span: Span<'a, T>,
+    size: usize,
+    /// Windows starting in `[front, back)` are left.
+    front: usize,
+    back: usize,
+}
+
+impl<'a, T: Sized> Windows<'a, T> {
+    fn window(&self, start: usize) -> Window<'a, T> {
+        Window::new(self.span.slice(start..start + self.size))
+    }
+}
+
+impl<T: Sized> Clone for Windows<'_, T> {
+    fn clone(&self) -> Self {
+        Self { ..*self }
+    }
+}
+
+impl<'a, T: Sized> Iterator for Windows<'a, T> {
+    type Item = Window<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        let res = self.window(self.front);
+        self.front += 1;
+        Some(res)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let size = self.back - self.front;
+        (size, Some(size))
+    }
+}
+
+impl<T: Sized> DoubleEndedIterator for Windows<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(self.window(self.back))
+    }
+}
+
+/// Copies a window into an array.
+fn gather<T: Copy, const N: usize>(window: Window<'_, T>) -> [T; N] {
+    match window {
+        Window::Contiguous(elems) => unsafe { elems.try_into().unwrap_unchecked() },
+        Window::Split(view) => {
+            let mut iter = view.iter();
+            array::from_fn(|_| unsafe { *iter.next().unwrap_unchecked() })
+        }
+    }
+}
+
+pub struct ArrayWindows<'a, T: Sized, const N: usize>(Windows<'a, T>);
+
+impl<T: Sized, const N: usize> Clone for ArrayWindows<'_, T, N> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T: Copy, const N: usize> Iterator for ArrayWindows<'_, T, N> {
+    type Item = [T; N];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(gather)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<T: Copy, const N: usize> DoubleEndedIterator for ArrayWindows<'_, T, N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(gather)
+    }
+}
+
+pub struct ArrayChunks<'a, T: Sized, const N: usize> {
+    span: Span<'a, T>,
+    /// Arrays at `[front, back)` are left.
+    front: usize,
+    back: usize,
+}
+
+impl<'a, T: Sized, const N: usize> ArrayChunks<'a, T, N> {
+    /// Returns the last `len % N` elements, which are not yielded.
+    pub fn remainder(&self) -> DequeSlice<'a, T> {
+        DequeView::new(self.span.slice(self.span.len / N * N..))
+    }
+
+    fn array(&self, n: usize) -> [T; N]
+    where
+        T: Copy,
+    {
+        gather(Window::new(self.span.slice(n * N..(n + 1) * N)))
+    }
+}
+
+impl<T: Sized, const N: usize> Clone for ArrayChunks<'_, T, N> {
+    fn clone(&self) -> Self {
+        Self { ..*self }
+    }
+}
+
+impl<T: Copy, const N: usize> Iterator for ArrayChunks<'_, T, N> {
+    type Item = [T; N];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        let res = self.array(self.front);
+        self.front += 1;
+        Some(res)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let size = self.back - self.front;
+        (size, Some(size))
+    }
+}
+
+impl<T: Copy, const N: usize> DoubleEndedIterator for ArrayChunks<'_, T, N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(self.array(self.back))
+    }
+}
+
+impl<T: Sized> ExactSizeIterator for Windows<'_, T> {}
+
+impl<T: Copy, const N: usize> ExactSizeIterator for ArrayWindows<'_, T, N> {}
+
+impl<T: Copy, const N: usize> ExactSizeIterator for ArrayChunks<'_, T, N> {}
+
+impl<T: Sized> FusedIterator for Windows<'_, T> {}
+
+impl<T: Copy, const N: usize> FusedIterator for ArrayWindows<'_, T, N> {}
+
+impl<T: Copy, const N: usize> FusedIterator for ArrayChunks<'_, T, N> {}
+
+unsafe impl<T: Sync> Send for Windows<'_, T> {}
+unsafe impl<T: Sync> Sync for Windows<'_, T> {}
+unsafe impl<T: Sync, const N: usize> Send for ArrayChunks<'_, T, N> {}
+unsafe impl<T: Sync, const N: usize> Sync for ArrayChunks<'_, T, N> {}